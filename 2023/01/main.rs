@@ -1,135 +1,451 @@
+use aoc::args;
+use aoc::digits::digits;
+use aoc::json::Json;
+use aoc::matcher::{Match, Matcher};
+use std::path::Path;
+use std::str::FromStr;
+use std::{fmt, fs, io};
+
 fn main() {
     let input = include_str!("input.txt");
+
+    // a word list replaces the built-in words, digits always count
+    let vocabulary = match args::value("--words") {
+        Some(path) => Vocabulary::from_file(&path)
+            .unwrap_or_else(|err| panic!("invalid word list {path}: {err}")),
+        None => Vocabulary::builtin(
+            args::parsed("--lang", Language::English),
+            args::flag("--zero"),
+            args::flag("--multi-digit"),
+        ),
+    };
+
+    // only the report, so it can be diffed as is
+    if let Some(format) = args::value("--explain") {
+        let format = format
+            .parse::<ExplainFormat>()
+            .unwrap_or_else(|err| panic!("{err}"));
+        println!("{}", format.render(&explain(input, &vocabulary.matcher())));
+        return;
+    }
+
     println!("Part 1 result is: {}", calibrate(input));
-    println!("Part 2 result is: {}", calibrate_with_words(input));
+    println!(
+        "Part 2 result is: {}",
+        calibrate_with_words(input, &vocabulary)
+    );
 }
 
-// part 1
-fn calibrate(input: &str) -> u32 {
-    let mut result = 0;
-
-    for line in input.lines() {
-        // iterate from front until the first numeric char appears add that to res multiplied
-        // by 10 because assuming as2df1g this should be 21 so the first number is basically
-        // 20 and the last one 1 making this 21
-        for char in line.chars() {
-            if char.is_numeric() {
-                let val: u32 = char.to_digit(10).unwrap().into();
-                result += val * 10;
-                break;
-            }
+const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Language {
+    English,
+    German,
+    French,
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "en" | "english" => Ok(Language::English),
+            "de" | "german" => Ok(Language::German),
+            "fr" | "french" => Ok(Language::French),
+            _ => Err(format!("Unknown language '{s}'")),
         }
+    }
+}
 
-        // for the second number we do exactly the same just backwards
-        for char in line.chars().rev() {
-            if char.is_numeric() {
-                let val: u32 = char.to_digit(10).unwrap().into();
-                result += val;
-                break;
+impl Language {
+    /// one to nine
+    fn digit_words(self) -> [&'static str; 9] {
+        match self {
+            Language::English => [
+                "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+            ],
+            Language::German => [
+                "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+            ],
+            Language::French => [
+                "un", "deux", "trois", "quatre", "cinq", "six", "sept", "huit", "neuf",
+            ],
+        }
+    }
+
+    fn zero(self) -> &'static str {
+        match self {
+            Language::English => "zero",
+            Language::German => "null",
+            Language::French => "zéro",
+        }
+    }
+
+    // only words that aren't simply written as a combination of smaller ones
+    fn multi_digit_words(self) -> &'static [(&'static str, u32)] {
+        match self {
+            Language::English => &[
+                ("ten", 10),
+                ("eleven", 11),
+                ("twelve", 12),
+                ("thirteen", 13),
+                ("fourteen", 14),
+                ("fifteen", 15),
+                ("sixteen", 16),
+                ("seventeen", 17),
+                ("eighteen", 18),
+                ("nineteen", 19),
+                ("twenty", 20),
+                ("thirty", 30),
+                ("forty", 40),
+                ("fifty", 50),
+                ("sixty", 60),
+                ("seventy", 70),
+                ("eighty", 80),
+                ("ninety", 90),
+                ("hundred", 100),
+            ],
+            Language::German => &[
+                ("zehn", 10),
+                ("elf", 11),
+                ("zwölf", 12),
+                ("dreizehn", 13),
+                ("vierzehn", 14),
+                ("fünfzehn", 15),
+                ("sechzehn", 16),
+                ("siebzehn", 17),
+                ("achtzehn", 18),
+                ("neunzehn", 19),
+                ("zwanzig", 20),
+                ("dreißig", 30),
+                ("vierzig", 40),
+                ("fünfzig", 50),
+                ("sechzig", 60),
+                ("siebzig", 70),
+                ("achtzig", 80),
+                ("neunzig", 90),
+                ("hundert", 100),
+            ],
+            Language::French => &[
+                ("dix", 10),
+                ("onze", 11),
+                ("douze", 12),
+                ("treize", 13),
+                ("quatorze", 14),
+                ("quinze", 15),
+                ("seize", 16),
+                ("vingt", 20),
+                ("trente", 30),
+                ("quarante", 40),
+                ("cinquante", 50),
+                ("soixante", 60),
+                ("cent", 100),
+            ],
+        }
+    }
+}
+
+#[derive(Debug)]
+enum VocabularyError {
+    Io(io::Error),
+    InvalidLine { line: usize, content: String },
+}
+
+impl fmt::Display for VocabularyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VocabularyError::Io(err) => write!(f, "{err}"),
+            VocabularyError::InvalidLine { line, content } => {
+                write!(f, "line {line} should be 'word value' but is '{content}'")
             }
         }
     }
+}
 
-    result
+/// Words that count as numbers, digits are always recognized on top of them
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Vocabulary {
+    words: Vec<(String, u32)>,
 }
 
-// part 2
-fn word_to_value(input: &str) -> Option<u32> {
-    match input {
-        "one" => Some(1),
-        "two" => Some(2),
-        "three" => Some(3),
-        "four" => Some(4),
-        "five" => Some(5),
-        "six" => Some(6),
-        "seven" => Some(7),
-        "eight" => Some(8),
-        "nine" => Some(9),
-        _ => None,
-    }
-}
-
-fn find_word_value(input: &str) -> Option<u32> {
-    let num = vec![
-        "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
-    ];
-
-    for n in num.iter() {
-        if !input.contains(n) {
-            continue;
+impl Vocabulary {
+    fn builtin(language: Language, zero: bool, multi_digit: bool) -> Self {
+        let mut words = (1..)
+            .zip(language.digit_words())
+            .map(|(value, word)| (word.to_string(), value))
+            .collect::<Vec<_>>();
+
+        if zero {
+            words.push((language.zero().to_string(), 0));
         }
 
-        if let Some(val) = word_to_value(n) {
-            return Some(val);
+        if multi_digit {
+            words.extend(
+                language
+                    .multi_digit_words()
+                    .iter()
+                    .map(|&(word, value)| (word.to_string(), value)),
+            );
         }
+
+        Vocabulary { words }
+    }
+
+    /// One `word value` pair per line, empty lines and lines starting with `#` are skipped
+    fn parse(text: &str) -> Result<Self, VocabularyError> {
+        let mut words = vec![];
+
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let invalid = || VocabularyError::InvalidLine {
+                line: index + 1,
+                content: line.to_string(),
+            };
+
+            let (word, value) = line.split_once(char::is_whitespace).ok_or_else(invalid)?;
+            let value = value.trim().parse().map_err(|_| invalid())?;
+
+            words.push((word.to_lowercase(), value));
+        }
+
+        Ok(Vocabulary { words })
+    }
+
+    fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, VocabularyError> {
+        let text = fs::read_to_string(path).map_err(VocabularyError::Io)?;
+        Vocabulary::parse(&text)
+    }
+
+    fn matcher(&self) -> Matcher<u32> {
+        Matcher::new(
+            DIGITS.iter().map(|&(digit, value)| (digit, value)).chain(
+                self.words
+                    .iter()
+                    .map(|(word, value)| (word.as_str(), *value)),
+            ),
+        )
     }
+}
 
-    None
+// english one to nine
+impl Default for Vocabulary {
+    fn default() -> Self {
+        Vocabulary::builtin(Language::English, false, false)
+    }
 }
 
-fn calibrate_with_words(input: &str) -> u32 {
-    let mut result = 0;
+// part 1
+fn calibrate(input: &str) -> u32 {
+    calibrate_with(input, &Matcher::new(DIGITS))
+}
 
-    for line in input.lines() {
-        let mut str = String::new();
+// part 2
+fn calibrate_with_words(input: &str, vocabulary: &Vocabulary) -> u32 {
+    calibrate_with(input, &vocabulary.matcher())
+}
 
-        // same as above from the front
-        for char in line.chars() {
-            // if str contains a valid value for word_to_value use that
-            if let Some(val) = word_to_value(str.as_str()) {
-                result += val * 10;
-                break;
-            }
+// the first digit of every line counts as tens and the last one as ones, assuming as2df1g this
+// should be 21. matches can overlap, so "eightwo" is 82, and words with several digits count
+// as if they were written as digits, so "twelve" is 12
+fn calibrate_with(input: &str, matcher: &Matcher<u32>) -> u32 {
+    input
+        .lines()
+        .filter_map(|line| matcher.first_and_last(line.as_bytes()))
+        .map(|(first, last)| line_value(*first.value, *last.value))
+        .sum()
+}
 
-            // same as above if its a number use that
-            if char.is_numeric() {
-                let val: u32 = char.to_digit(10).unwrap().into();
-                result += val * 10;
-                break;
-            }
+fn line_value(first: u32, last: u32) -> u32 {
+    digits(first, 10).next().unwrap() * 10 + last % 10
+}
 
-            // if not, add current char to str
-            str.push(char);
+/// A digit or number word found in a line
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Found<'a> {
+    text: &'a str,
+    /// in bytes from the start of the line
+    offset: usize,
+    value: u32,
+}
 
-            if let Some(val) = find_word_value(str.as_str()) {
-                result += val * 10;
-                break;
-            }
+impl Found<'_> {
+    fn kind(&self) -> &'static str {
+        match self.text.bytes().all(|b| b.is_ascii_digit()) {
+            true => "digit",
+            false => "word",
         }
+    }
 
-        let mut str = String::new();
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("text", self.text.into()),
+            ("offset", self.offset.into()),
+            ("kind", self.kind().into()),
+            ("value", self.value.into()),
+        ])
+    }
+}
 
-        // same as above from the back
-        for char in line.chars().rev() {
-            // if str contains a valid value for word_to_value use that
-            if let Some(val) = word_to_value(str.as_str()) {
-                result += val;
-                break;
-            }
+impl fmt::Display for Found<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} @{} {}", self.text, self.offset, self.kind())
+    }
+}
 
-            // same as above if its a number use that
-            if char.is_numeric() {
-                let val: u32 = char.to_digit(10).unwrap().into();
-                result += val;
-                break;
-            }
+/// How a single line was read
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineReport<'a> {
+    /// starting at 1
+    number: usize,
+    text: &'a str,
+    first: Option<Found<'a>>,
+    last: Option<Found<'a>>,
+}
+
+impl LineReport<'_> {
+    fn value(&self) -> u32 {
+        match (&self.first, &self.last) {
+            (Some(first), Some(last)) => line_value(first.value, last.value),
+            _ => 0,
+        }
+    }
+
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("line", self.number.into()),
+            ("text", self.text.into()),
+            ("first", self.first.as_ref().map(Found::to_json).into()),
+            ("last", self.last.as_ref().map(Found::to_json).into()),
+            ("value", self.value().into()),
+        ])
+    }
+}
+
+fn explain<'a>(input: &'a str, matcher: &Matcher<u32>) -> Vec<LineReport<'a>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(index, line)| {
+            let found = |m: Match<u32>| Found {
+                text: &line[m.start..m.end],
+                offset: m.start,
+                value: *m.value,
+            };
 
-            // if not, add current char to str at the font
-            str.insert(0, char);
+            let (first, last) = matcher.first_and_last(line.as_bytes()).unzip();
 
-            if let Some(val) = find_word_value(str.as_str()) {
-                result += val;
-                break;
+            LineReport {
+                number: index + 1,
+                text: line,
+                first: first.map(found),
+                last: last.map(found),
             }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExplainFormat {
+    Table,
+    Json,
+}
+
+impl FromStr for ExplainFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(ExplainFormat::Table),
+            "json" => Ok(ExplainFormat::Json),
+            _ => Err(format!("Unknown explain format '{s}', use table or json")),
         }
     }
+}
+
+impl ExplainFormat {
+    fn render(self, reports: &[LineReport]) -> String {
+        let total = reports.iter().map(LineReport::value).sum::<u32>();
 
-    result
+        match self {
+            ExplainFormat::Table => explain_table(reports, total),
+            ExplainFormat::Json => Json::object([
+                ("total", total.into()),
+                (
+                    "lines",
+                    Json::Array(reports.iter().map(LineReport::to_json).collect()),
+                ),
+            ])
+            .pretty(),
+        }
+    }
+}
+
+fn explain_table(reports: &[LineReport], total: u32) -> String {
+    let describe = |found: &Option<Found>| found.as_ref().map_or("-".to_string(), Found::to_string);
+
+    let rows = reports
+        .iter()
+        .map(|report| {
+            [
+                report.number.to_string(),
+                describe(&report.first),
+                describe(&report.last),
+                report.value().to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let header = ["line", "first", "last", "value"].map(String::from);
+    let widths = (0..header.len())
+        .map(|col| {
+            std::iter::once(&header)
+                .chain(&rows)
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap()
+        })
+        .collect::<Vec<_>>();
+
+    let mut out = std::iter::once(&header)
+        .chain(&rows)
+        .map(|[line, first, last, value]| {
+            format!(
+                "{line:>w0$}  {first:<w1$}  {last:<w2$}  {value:>w3$}",
+                w0 = widths[0],
+                w1 = widths[1],
+                w2 = widths[2],
+                w3 = widths[3],
+            )
+        })
+        .collect::<Vec<_>>();
+
+    out.push(format!("total: {total}"));
+    out.join("\n")
 }
 
 #[cfg(test)]
 mod test_2023_01 {
-    use crate::{calibrate, calibrate_with_words};
+    use crate::{
+        calibrate, calibrate_with_words, explain, ExplainFormat, Language, Vocabulary,
+        VocabularyError,
+    };
 
     const TEST_INPUT_CALIBRATE: &str = "1abc2
 pqr3stu8vwx
@@ -139,6 +455,7 @@ treb7uchet";
     #[test]
     fn test_calibrate() {
         assert_eq!(calibrate(TEST_INPUT_CALIBRATE), 142);
+        assert_eq!(calibrate("a0b1"), 1);
     }
 
     const TEST_INPUT_CALIBRATE_WITH_WORDS: &str = "two1nine
@@ -151,6 +468,128 @@ zoneight234
 
     #[test]
     fn test_calibrate_with_words() {
-        assert_eq!(calibrate_with_words(TEST_INPUT_CALIBRATE_WITH_WORDS), 281);
+        assert_eq!(
+            calibrate_with_words(TEST_INPUT_CALIBRATE_WITH_WORDS, &Vocabulary::default()),
+            281
+        );
+    }
+
+    #[test]
+    fn test_overlapping_words() {
+        let english = Vocabulary::default();
+
+        assert_eq!(calibrate_with_words("eightwo", &english), 82);
+        assert_eq!(calibrate_with_words("xtwone", &english), 21);
+        assert_eq!(calibrate_with_words("oneight\nsevenine", &english), 18 + 79);
+        assert_eq!(calibrate_with_words("five", &english), 55);
+        assert_eq!(calibrate_with_words("nothing here", &english), 0);
+    }
+
+    #[test]
+    fn test_languages() {
+        let cases = [
+            ("seventeen", Language::English, false, false, 77),
+            ("seventeen", Language::English, false, true, 17),
+            ("zeroxfive", Language::English, false, false, 55),
+            ("zeroxfive", Language::English, true, false, 5),
+            ("zweiundvierzig", Language::German, false, false, 24),
+            ("zweiundvierzig", Language::German, false, true, 20),
+            ("nullundeinsacht", Language::German, false, false, 18),
+            ("zéro et huit", Language::French, true, false, 8),
+            ("douze", Language::French, false, true, 12),
+        ];
+
+        for (input, language, zero, multi_digit, expected) in cases {
+            let vocabulary = Vocabulary::builtin(language, zero, multi_digit);
+            assert_eq!(
+                expected,
+                calibrate_with_words(input, &vocabulary),
+                "{input}"
+            );
+        }
+
+        assert_eq!(Ok(Language::German), "de".parse());
+        assert!("klingon".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn test_custom_vocabulary() {
+        let spanish = Vocabulary::parse("# spanish\n\nuno 1\n  dos\t2\nveinte 20\n").unwrap();
+
+        assert_eq!(3, spanish.words.len());
+        assert_eq!(10, calibrate_with_words("unoxveinte", &spanish));
+        assert_eq!(22, calibrate_with_words("dos", &spanish));
+        assert_eq!(0, calibrate_with_words("one", &spanish));
+
+        assert!(matches!(
+            Vocabulary::parse("uno 1\ntres"),
+            Err(VocabularyError::InvalidLine { line: 2, .. })
+        ));
+        assert!(matches!(
+            Vocabulary::parse("uno one"),
+            Err(VocabularyError::InvalidLine { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_explain() {
+        let matcher = Vocabulary::default().matcher();
+        let reports = explain("two1nine\nnothing\nxtwone3", &matcher);
+
+        assert_eq!(3, reports.len());
+        assert_eq!(29, reports[0].value());
+        assert_eq!(0, reports[1].value());
+
+        let first = reports[2].first.as_ref().unwrap();
+        let last = reports[2].last.as_ref().unwrap();
+        assert_eq!(("two", 1, "word"), (first.text, first.offset, first.kind()));
+        assert_eq!(("3", 6, "digit"), (last.text, last.offset, last.kind()));
+
+        assert_eq!(
+            "line  first        last          value
+   1  two @0 word  nine @4 word     29
+   2  -            -                 0
+   3  two @1 word  3 @6 digit       23
+total: 52",
+            ExplainFormat::Table.render(&reports)
+        );
+
+        let json = ExplainFormat::Json.render(&reports[1..2]);
+        assert_eq!(
+            r#"{
+  "total": 0,
+  "lines": [
+    {
+      "line": 2,
+      "text": "nothing",
+      "first": null,
+      "last": null,
+      "value": 0
+    }
+  ]
+}"#,
+            json
+        );
+        assert!(ExplainFormat::Json.render(&reports).contains(
+            r#""first": {
+        "text": "two",
+        "offset": 1,
+        "kind": "word",
+        "value": 2
+      }"#
+        ));
+    }
+
+    #[test]
+    fn test_large_document() {
+        // a few megabytes with long lines
+        let line = format!("{}twone{}eightwo\n", "x".repeat(20_000), "y".repeat(20_000));
+        let input = line.repeat(100);
+
+        assert_eq!(
+            calibrate_with_words(&input, &Vocabulary::default()),
+            100 * 22
+        );
+        assert_eq!(calibrate(&input), 0);
     }
 }
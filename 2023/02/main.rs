@@ -1,114 +1,553 @@
+use aoc::args;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+
 fn main() {
     let file = include_str!("./input.txt");
 
-    let limits = (12, 13, 14);
+    let limits = args::values("--limit");
+    let limits = match limits.is_empty() {
+        true => Set::from_iter([("red", 12), ("green", 13), ("blue", 14)]),
+        false => Set::parse_limits(&limits).unwrap_or_else(|err| panic!("{err}")),
+    };
+
+    let games = file
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            line.parse::<Game>()
+                .unwrap_or_else(|err| panic!("line {}: {err}", i + 1))
+        })
+        .collect::<Vec<_>>();
+
+    if args::flag("--normalize") {
+        for game in &games {
+            println!("{game}");
+        }
+        return;
+    }
+
+    let colors = games
+        .iter()
+        .flat_map(|game| game.colors())
+        .collect::<BTreeSet<_>>();
 
     let mut sum_ids = 0;
     let mut num_required_cubes = 0;
 
-    for line in file.lines() {
-        if let Some(game) = Game::from_line(line) {
-            if game.fits(limits.0, limits.1, limits.2) {
-                sum_ids += game.number;
-            }
-
-            let (r, g, b) = game.max_cubes();
-            num_required_cubes += r * g * b;
+    for game in &games {
+        if game.fits(&limits) {
+            sum_ids += game.number;
         }
+
+        num_required_cubes += game.max_cubes().power(&colors);
     }
 
     println!("Sum of IDs: {sum_ids}");
     println!("Num required cubes: {num_required_cubes}");
+
+    if let Some(percent) = args::value("--smallest-bag") {
+        let percent = percent.parse().expect("percent should be a number");
+
+        match smallest_bag(&games, percent) {
+            Some(bag) => println!(
+                "Smallest bag admitting {percent}% of games: {bag} ({} cubes)",
+                bag.total()
+            ),
+            None => println!("No bag admits {percent}% of games"),
+        }
+    }
+
+    if args::flag("--binding") {
+        for rejection in rejections(&games, &limits) {
+            println!("{rejection}");
+        }
+    }
+
+    if args::flag("--frontier") {
+        println!("Loosest limits with the same sum of IDs:");
+
+        for bounds in frontier(&games, &limits) {
+            println!("  {}", format_bounds(&bounds));
+        }
+    }
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Game {
     number: usize,
     sets: Vec<Set>,
 }
 
-pub struct Set(usize, usize, usize);
+/// Number of cubes per color, colors that aren't in the set count as zero
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Set(BTreeMap<String, usize>);
+
+impl Set {
+    fn get(&self, color: &str) -> usize {
+        self.0.get(color).copied().unwrap_or(0)
+    }
+
+    fn add(&mut self, color: &str, number: usize) {
+        *self.0.entry(color.to_string()).or_insert(0) += number;
+    }
+
+    fn colors(&self) -> impl Iterator<Item = &str> {
+        self.0.keys().map(String::as_str)
+    }
+
+    /// Number of cubes over all colors
+    fn total(&self) -> usize {
+        self.0.values().sum()
+    }
+
+    /// Product of the counts of all `colors`, zero if one of them is missing
+    fn power<S: AsRef<str>>(&self, colors: &BTreeSet<S>) -> usize {
+        colors
+            .iter()
+            .map(|color| self.get(color.as_ref()))
+            .product()
+    }
+
+    /// True if no color exceeds its limit, colors without a limit can't be used at all
+    fn fits(&self, limits: &Set) -> bool {
+        self.0
+            .iter()
+            .all(|(color, &number)| number <= limits.get(color))
+    }
+
+    /// Highest count of every color in both sets
+    fn max(mut self, other: &Set) -> Set {
+        for (color, &number) in &other.0 {
+            let max = self.0.entry(color.clone()).or_insert(0);
+            *max = (*max).max(number);
+        }
+
+        self
+    }
+
+    /// Limits given as `color=count`
+    fn parse_limits<S: AsRef<str>>(limits: &[S]) -> Result<Set, String> {
+        let mut set = Set::default();
+
+        for limit in limits {
+            let limit = limit.as_ref();
+            let (color, number) = limit
+                .split_once('=')
+                .and_then(|(color, number)| Some((color.trim(), number.trim().parse().ok()?)))
+                .ok_or_else(|| format!("Invalid limit '{limit}', expected color=count"))?;
+
+            set.0.insert(color.to_string(), number);
+        }
+
+        Ok(set)
+    }
+}
+
+impl fmt::Display for Set {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let counts = self
+            .0
+            .iter()
+            .map(|(color, number)| format!("{number} {color}"))
+            .collect::<Vec<_>>();
+
+        write!(f, "{}", counts.join(", "))
+    }
+}
+
+impl<'a> FromIterator<(&'a str, usize)> for Set {
+    fn from_iter<I: IntoIterator<Item = (&'a str, usize)>>(iter: I) -> Self {
+        let mut set = Set::default();
+
+        for (color, number) in iter {
+            set.add(color, number);
+        }
+
+        set
+    }
+}
 
 impl Game {
-    fn from_line(line: &str) -> Option<Game> {
-        if !line.starts_with("Game") {
-            return None;
+    /// Every color that shows up in any set
+    fn colors(&self) -> impl Iterator<Item = &str> {
+        self.sets.iter().flat_map(|set| set.colors())
+    }
+
+    fn max_cubes(&self) -> Set {
+        self.sets
+            .iter()
+            .fold(Set::default(), |max, set| max.max(set))
+    }
+
+    fn fits(&self, limits: &Set) -> bool {
+        self.max_cubes().fits(limits)
+    }
+}
+
+/// Why a line isn't a valid game, `column` counts chars from 1
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    column: usize,
+    kind: ParseErrorKind,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    MissingPrefix,
+    MissingColon,
+    InvalidNumber(String),
+    MissingColor,
+    UnexpectedText(String),
+    DuplicateColor(String),
+    EmptyDraw,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: ", self.column)?;
+
+        match &self.kind {
+            ParseErrorKind::MissingPrefix => write!(f, "expected 'Game'"),
+            ParseErrorKind::MissingColon => write!(f, "expected ':' after the game number"),
+            ParseErrorKind::InvalidNumber(text) => write!(f, "'{text}' is not a number"),
+            ParseErrorKind::MissingColor => write!(f, "expected a color after the count"),
+            ParseErrorKind::UnexpectedText(text) => write!(f, "unexpected '{text}'"),
+            ParseErrorKind::DuplicateColor(color) => {
+                write!(f, "{color} appears twice in the same set")
+            }
+            ParseErrorKind::EmptyDraw => write!(f, "expected a count and a color"),
         }
+    }
+}
+
+// `part` has to be a slice of `line`
+fn column(line: &str, part: &str) -> usize {
+    let offset = part.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
 
-        let mut parts = line.split(":");
-        let game_part = parts.next().expect("should have game part");
-        let sets_part = parts.next().expect("should have sets part");
+fn parse_number(line: &str, text: &str) -> Result<usize, ParseError> {
+    text.parse().map_err(|_| ParseError {
+        column: column(line, text),
+        kind: ParseErrorKind::InvalidNumber(text.to_string()),
+    })
+}
 
-        let game_number: usize = game_part
-            .split(" ")
-            .last()
-            .expect("should have game number")
-            .parse()
-            .expect("should be digit");
+impl FromStr for Game {
+    type Err = ParseError;
 
-        let mut game = Self {
-            number: game_number,
-            sets: vec![],
+    /// `Game <number>: <count> <color>, ...; ...`, every `;` separated part is one set
+    fn from_str(line: &str) -> Result<Self, Self::Err> {
+        let error = |part: &str, kind| ParseError {
+            column: column(line, part),
+            kind,
         };
 
-        for set_part in sets_part.split(";") {
-            let mut red = 0;
-            let mut green = 0;
-            let mut blue = 0;
+        let rest = line
+            .strip_prefix("Game ")
+            .ok_or_else(|| error(line, ParseErrorKind::MissingPrefix))?;
+        let (number, sets_part) = rest
+            .split_once(':')
+            .ok_or_else(|| error(&line[line.len()..], ParseErrorKind::MissingColon))?;
+        let number = parse_number(line, number.trim())?;
 
-            for item_pairs in set_part.trim().split(",") {
-                let mut item_pair = item_pairs.trim().split(" ");
-                let number: usize = item_pair
-                    .next()
-                    .expect("should have cube number")
-                    .parse()
-                    .expect("cube number should be a  number");
-                let color = item_pair.next().expect("should have cube color");
-
-                match color {
-                    "red" => red += number,
-                    "green" => green += number,
-                    "blue" => blue += number,
-                    unknown => panic!("Unknown color name: {}", unknown),
+        let mut sets = vec![];
+
+        for set_part in sets_part.split(';') {
+            let mut set = Set::default();
+
+            for draw in set_part.split(',') {
+                let draw = draw.trim();
+                let mut words = draw.split_whitespace();
+
+                let Some(count) = words.next() else {
+                    return Err(error(draw, ParseErrorKind::EmptyDraw));
                 };
+                let count = parse_number(line, count)?;
+
+                let color = words
+                    .next()
+                    .ok_or_else(|| error(&draw[draw.len()..], ParseErrorKind::MissingColor))?;
+
+                if let Some(extra) = words.next() {
+                    return Err(error(
+                        extra,
+                        ParseErrorKind::UnexpectedText(extra.to_string()),
+                    ));
+                }
+
+                if set.0.contains_key(color) {
+                    return Err(error(
+                        color,
+                        ParseErrorKind::DuplicateColor(color.to_string()),
+                    ));
+                }
+
+                set.add(color, count);
             }
 
-            game.sets.push(Set(red, green, blue));
+            sets.push(set);
         }
 
-        Some(game)
+        Ok(Game { number, sets })
     }
+}
 
-    fn max_cubes(&self) -> (usize, usize, usize) {
-        let mut max_red = 0;
-        let mut max_green = 0;
-        let mut max_blue = 0;
+/// The canonical line, colors of a set in alphabetical order
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sets = self.sets.iter().map(Set::to_string).collect::<Vec<_>>();
 
-        for set in self.sets.iter() {
-            if set.0 > max_red {
-                max_red = set.0;
-            }
+        write!(f, "Game {}: {}", self.number, sets.join("; "))
+    }
+}
 
-            if set.1 > max_green {
-                max_green = set.1;
-            }
+/// Smallest bag by total number of cubes that admits at least `percent` percent of the games,
+/// ties go to the first one found
+fn smallest_bag(games: &[Game], percent: usize) -> Option<Set> {
+    let maxima = games.iter().map(Game::max_cubes).collect::<Vec<_>>();
+    let colors = maxima
+        .iter()
+        .flat_map(|max| max.colors().map(str::to_string))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    let needed = (games.len() * percent).div_ceil(100);
+
+    if needed > games.len() {
+        return None;
+    }
 
-            if set.2 > max_blue {
-                max_blue = set.2;
-            }
+    let mut best = None;
+    let admitted = maxima.iter().collect::<Vec<_>>();
+    smallest_bag_from(&admitted, &colors, needed, Set::default(), &mut best);
+
+    best
+}
+
+// tries every useful limit for the first color, the last one simply takes the lowest limit that
+// still admits enough games
+fn smallest_bag_from(
+    admitted: &[&Set],
+    colors: &[String],
+    needed: usize,
+    bag: Set,
+    best: &mut Option<Set>,
+) {
+    if best
+        .as_ref()
+        .is_some_and(|best| best.total() <= bag.total())
+    {
+        return;
+    }
+
+    let Some((color, rest)) = colors.split_first() else {
+        *best = Some(bag);
+        return;
+    };
+
+    let mut counts = admitted
+        .iter()
+        .map(|max| max.get(color))
+        .collect::<Vec<_>>();
+    counts.sort_unstable();
+
+    if rest.is_empty() {
+        let limit = needed.checked_sub(1).map_or(0, |i| counts[i]);
+        let mut bag = bag;
+        bag.0.insert(color.clone(), limit);
+
+        return smallest_bag_from(&[], rest, needed, bag, best);
+    }
+
+    counts.insert(0, 0);
+    counts.dedup();
+
+    for limit in counts {
+        let admitted = admitted
+            .iter()
+            .copied()
+            .filter(|max| max.get(color) <= limit)
+            .collect::<Vec<_>>();
+
+        if admitted.len() < needed {
+            continue;
+        }
+
+        let mut bag = bag.clone();
+        bag.0.insert(color.clone(), limit);
+        smallest_bag_from(&admitted, rest, needed, bag, best);
+    }
+}
+
+/// Game that doesn't fit the limits
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rejection {
+    number: usize,
+    /// every color over its limit as (color, cubes needed, limit)
+    exceeded: Vec<(String, usize, usize)>,
+}
+
+impl Rejection {
+    /// The color whose limit alone keeps the game out, None if more than one has to be raised
+    fn binding(&self) -> Option<&str> {
+        match self.exceeded.as_slice() {
+            [(color, _, _)] => Some(color),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exceeded = self
+            .exceeded
+            .iter()
+            .map(|(color, needed, limit)| format!("{color} {needed} > {limit}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        match self.binding() {
+            Some(color) => write!(f, "Game {}: bound by {color} ({exceeded})", self.number),
+            None => write!(
+                f,
+                "Game {}: no single binding color ({exceeded})",
+                self.number
+            ),
         }
+    }
+}
+
+fn rejections(games: &[Game], limits: &Set) -> Vec<Rejection> {
+    games
+        .iter()
+        .filter(|game| !game.fits(limits))
+        .map(|game| Rejection {
+            number: game.number,
+            exceeded: game
+                .max_cubes()
+                .0
+                .into_iter()
+                .filter(|(color, number)| *number > limits.get(color))
+                .map(|(color, number)| {
+                    let limit = limits.get(&color);
+                    (color, number, limit)
+                })
+                .collect(),
+        })
+        .collect()
+}
+
+/// Limits per color, None means unbounded
+type Bounds = BTreeMap<String, Option<usize>>;
+
+/// Loosest limits that give the same sum of IDs as `limits`, the games adding up to it may be
+/// different ones. None of the limits can be raised any further without changing the sum.
+fn frontier(games: &[Game], limits: &Set) -> Vec<Bounds> {
+    let maxima = games
+        .iter()
+        .map(|game| (game.number, game.max_cubes()))
+        .collect::<Vec<_>>();
+    let target = maxima
+        .iter()
+        .filter(|(_, max)| max.fits(limits))
+        .map(|(number, _)| number)
+        .sum();
+    let colors = games
+        .iter()
+        .flat_map(|game| game.colors().map(str::to_string))
+        .chain(limits.colors().map(str::to_string))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    let mut points = vec![];
+    let candidates = maxima.iter().collect::<Vec<_>>();
+    frontier_from(&candidates, &colors, target, Bounds::new(), &mut points);
+
+    // drop every point another one is at least as loose as in every color
+    let loose = |bound: &Option<usize>| bound.unwrap_or(usize::MAX);
+    let dominates = |a: &Bounds, b: &Bounds| {
+        a != b
+            && a.values()
+                .zip(b.values())
+                .all(|(a, b)| loose(a) >= loose(b))
+    };
+
+    points.sort();
+    points.dedup();
+    points
+        .iter()
+        .filter(|point| !points.iter().any(|other| dominates(other, point)))
+        .cloned()
+        .collect()
+}
 
-        (max_red, max_green, max_blue)
+// `candidates` are the games within the limits picked so far. a limit that can't be raised sits
+// just below what some candidate needs, so only those values and unbounded are tried.
+fn frontier_from(
+    candidates: &[&(usize, Set)],
+    colors: &[String],
+    target: usize,
+    bounds: Bounds,
+    points: &mut Vec<Bounds>,
+) {
+    // the sum only grows with the limits, so it ends up between admitting the games that need
+    // none of the remaining colors and admitting all candidates
+    let most = candidates.iter().map(|(number, _)| number).sum::<usize>();
+    let least = candidates
+        .iter()
+        .filter(|(_, max)| colors.iter().all(|color| max.get(color) == 0))
+        .map(|(number, _)| number)
+        .sum::<usize>();
+
+    if most < target || least > target {
+        return;
     }
 
-    fn fits(&self, red: usize, green: usize, blue: usize) -> bool {
-        let (r, g, b) = self.max_cubes();
-        r <= red && g <= green && b <= blue
+    let Some((color, rest)) = colors.split_first() else {
+        points.push(bounds);
+        return;
+    };
+
+    let mut limits = candidates
+        .iter()
+        .filter_map(|(_, max)| max.get(color).checked_sub(1))
+        .collect::<Vec<_>>();
+    limits.sort_unstable();
+    limits.dedup();
+
+    for limit in limits.into_iter().map(Some).chain([None]) {
+        let candidates = candidates
+            .iter()
+            .copied()
+            .filter(|(_, max)| limit.is_none_or(|limit| max.get(color) <= limit))
+            .collect::<Vec<_>>();
+
+        let mut bounds = bounds.clone();
+        bounds.insert(color.clone(), limit);
+        frontier_from(&candidates, rest, target, bounds, points);
     }
 }
 
+fn format_bounds(bounds: &Bounds) -> String {
+    bounds
+        .iter()
+        .map(|(color, limit)| match limit {
+            Some(limit) => format!("{color} <= {limit}"),
+            None => format!("{color} any"),
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[cfg(test)]
 mod test_2023_02 {
-    use crate::Game;
+    use crate::{frontier, rejections, smallest_bag, Game, ParseError, ParseErrorKind, Set};
+    use std::collections::BTreeSet;
 
     const TEST_LINES: [(&str, (usize, usize, usize)); 5] = [
         (
@@ -136,9 +575,10 @@ mod test_2023_02 {
     #[test]
     fn test_parsing_lines() {
         for (line, (expected_red, expected_green, expected_blue)) in TEST_LINES.iter() {
-            let game = Game::from_line(line).expect("should parse");
+            let game = line.parse::<Game>().expect("should parse");
 
-            let (r, g, b) = game.max_cubes();
+            let max = game.max_cubes();
+            let (r, g, b) = (max.get("red"), max.get("green"), max.get("blue"));
 
             assert_eq!(
                 *expected_red, r,
@@ -172,10 +612,15 @@ mod test_2023_02 {
         let mut sum = 0;
 
         for (line, (red_cubes, green_cubes, blue_cubes), expect_fits) in TEST_LINES_FIT.iter() {
-            let game = Game::from_line(line).expect("should parse");
+            let game = line.parse::<Game>().expect("should parse");
 
-            let (r, g, b) = game.max_cubes();
-            let fits = game.fits(*red_cubes, *green_cubes, *blue_cubes);
+            let max = game.max_cubes();
+            let (r, g, b) = (max.get("red"), max.get("green"), max.get("blue"));
+            let fits = game.fits(&Set::from_iter([
+                ("red", *red_cubes),
+                ("green", *green_cubes),
+                ("blue", *blue_cubes),
+            ]));
 
             assert_eq!(
                 *expect_fits, fits,
@@ -190,4 +635,176 @@ mod test_2023_02 {
 
         assert_eq!(TEST_LINES_POSSIBLE_ID_SUM, sum);
     }
+
+    #[test]
+    fn test_any_colors() {
+        let game = "Game 7: 2 yellow, 1 red; 4 yellow; 3 teal, 1 yellow"
+            .parse::<Game>()
+            .expect("should parse");
+
+        let max = game.max_cubes();
+        assert_eq!(
+            (4, 1, 3, 0),
+            (
+                max.get("yellow"),
+                max.get("red"),
+                max.get("teal"),
+                max.get("blue")
+            )
+        );
+
+        let limits = Set::parse_limits(&["yellow=4", "red = 1", "teal=3"]).unwrap();
+        assert!(game.fits(&limits));
+
+        // colors without a limit can't be used
+        let limits = Set::parse_limits(&["yellow=4", "red=1"]).unwrap();
+        assert!(!game.fits(&limits));
+
+        let colors = game.colors().collect::<BTreeSet<_>>();
+        assert_eq!(12, max.power(&colors));
+        assert_eq!(0, max.power(&BTreeSet::from(["red", "blue"])));
+
+        assert!(Set::parse_limits(&["red"]).is_err());
+        assert!(Set::parse_limits(&["red=many"]).is_err());
+    }
+
+    fn test_games() -> Vec<Game> {
+        TEST_LINES
+            .iter()
+            .map(|(line, _)| line.parse::<Game>().expect("should parse"))
+            .collect()
+    }
+
+    #[test]
+    fn test_smallest_bag() {
+        let games = test_games();
+
+        let bag = smallest_bag(&games, 100).unwrap();
+        assert_eq!(
+            Set::from_iter([("red", 20), ("green", 13), ("blue", 15)]),
+            bag
+        );
+
+        // games 1, 2 and 5
+        let bag = smallest_bag(&games, 60).unwrap();
+        assert_eq!(Set::from_iter([("red", 6), ("green", 3), ("blue", 6)]), bag);
+        assert_eq!(15, bag.total());
+
+        assert_eq!(0, smallest_bag(&games, 0).unwrap().total());
+        assert_eq!(None, smallest_bag(&games, 101));
+    }
+
+    #[test]
+    fn test_rejections() {
+        let limits = Set::from_iter([("red", 12), ("green", 13), ("blue", 14)]);
+        let rejected = rejections(&test_games(), &limits);
+
+        assert_eq!(
+            vec![(3, Some("red")), (4, None)],
+            rejected
+                .iter()
+                .map(|r| (r.number, r.binding()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![("blue".to_string(), 15, 14), ("red".to_string(), 14, 12)],
+            rejected[1].exceeded
+        );
+        assert_eq!(
+            "Game 3: bound by red (red 20 > 12)",
+            rejected[0].to_string()
+        );
+    }
+
+    #[test]
+    fn test_frontier() {
+        let limits = Set::from_iter([("red", 12), ("green", 13), ("blue", 14)]);
+
+        // as (blue, green, red)
+        let points = frontier(&test_games(), &limits)
+            .into_iter()
+            .map(|bounds| bounds.into_values().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                vec![None, None, Some(13)],
+                vec![Some(14), None, Some(19)],
+                vec![Some(14), Some(12), None],
+            ],
+            points
+        );
+
+        // game 3 alone adds up to the same sum as games 1 and 2
+        let games = ["Game 1: 2 red", "Game 2: 2 blue", "Game 3: 5 green"]
+            .iter()
+            .map(|line| line.parse::<Game>().expect("should parse"))
+            .collect::<Vec<_>>();
+        let limits = Set::from_iter([("red", 2), ("green", 0), ("blue", 2)]);
+        assert_eq!(
+            vec![vec![None, Some(4), None], vec![Some(1), None, Some(1)]],
+            frontier(&games, &limits)
+                .into_iter()
+                .map(|bounds| bounds.into_values().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+
+        // nothing to keep out
+        let limits = Set::from_iter([("red", 20), ("green", 13), ("blue", 15)]);
+        assert_eq!(
+            vec![vec![None, None, None]],
+            frontier(&test_games(), &limits)
+                .into_iter()
+                .map(|bounds| bounds.into_values().collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for (line, _) in TEST_LINES {
+            let game = line.parse::<Game>().expect("should parse");
+            let printed = game.to_string();
+
+            assert_eq!(game, printed.parse::<Game>().expect("should parse"));
+            assert_eq!(printed, printed.parse::<Game>().unwrap().to_string());
+        }
+
+        let game = "Game 12:1 red ,3  blue;  0 green".parse::<Game>().unwrap();
+        assert_eq!("Game 12: 3 blue, 1 red; 0 green", game.to_string());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let error = |line: &str| line.parse::<Game>().unwrap_err();
+        let at = |column, kind| ParseError { column, kind };
+
+        assert_eq!(at(1, ParseErrorKind::MissingPrefix), error("Card 1: 1 red"));
+        assert_eq!(at(8, ParseErrorKind::MissingColon), error("Game 12"));
+        assert_eq!(
+            at(6, ParseErrorKind::InvalidNumber("x".to_string())),
+            error("Game x: 1 red")
+        );
+        assert_eq!(
+            at(16, ParseErrorKind::InvalidNumber("two".to_string())),
+            error("Game 1: 1 red; two blue")
+        );
+        assert_eq!(
+            at(17, ParseErrorKind::MissingColor),
+            error("Game 1: 1 red, 2")
+        );
+        assert_eq!(
+            at(16, ParseErrorKind::UnexpectedText("red".to_string())),
+            error("Game 1: 1 dark red")
+        );
+        assert_eq!(
+            at(18, ParseErrorKind::DuplicateColor("red".to_string())),
+            error("Game 1: 1 red, 2 red")
+        );
+        assert_eq!(at(15, ParseErrorKind::EmptyDraw), error("Game 1: 1 red;"));
+
+        assert_eq!(
+            "column 16: 'two' is not a number",
+            error("Game 1: 1 red; two blue").to_string()
+        );
+    }
 }
@@ -1,25 +1,62 @@
 use crate::SchematicError::EmptyString;
-use std::collections::HashMap;
+use aoc::ansi::{render, Color, ColorMode, Style};
+use aoc::args;
+use aoc::digits::from_digits;
+use aoc::grid::{Connectivity, Grid2D};
+use aoc::image::{Image, Rgb};
+use aoc::json::Json;
+use aoc::region::{label, Labels, Region};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::str::FromStr;
 
 fn main() {
     let input_str = include_str!("./input.txt");
-    let schematic = Schematic::from_str(input_str).expect("should parse");
+    let classes =
+        SymbolClasses::parse(&args::values("--class")).unwrap_or_else(|err| panic!("{err}"));
+    let rule = args::parsed("--gear-rule", GearRule::default());
+    let schematic = Schematic::parse(input_str, &classes).expect("should parse");
+
+    if args::flag("--json") {
+        println!("{}", schematic.to_json(&rule).pretty());
+        return;
+    }
+
     println!("Part 1) Result: {}", schematic.sum());
-    println!("Part 2) Gear Part Sum: {}", schematic.gear_part_sum());
+    println!("Part 2) Gear Part Sum: {}", schematic.gear_part_sum(&rule));
+
+    if args::flag("--render") {
+        println!("{}", schematic.render(ColorMode::Auto));
+    }
+
+    if let Some(path) = args::value("--export") {
+        let scale = args::parsed("--scale", 4);
+        schematic
+            .image(scale)
+            .save(&path)
+            .expect("should write the image");
+    }
 }
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone)]
 enum Value {
     Empty,
-    Symbol,
-    Gear,
+    Symbol(char),
+    Gear(char),
     Digit(usize),
 }
 
 impl Value {
     fn is_symbol(&self) -> bool {
-        *self == Value::Symbol || *self == Value::Gear
+        matches!(self, Value::Symbol(_) | Value::Gear(_))
+    }
+
+    fn to_char(self) -> char {
+        match self {
+            Value::Empty => '.',
+            Value::Symbol(c) => c,
+            Value::Gear(c) => c,
+            Value::Digit(digit) => char::from_digit(digit as u32, 10).unwrap(),
+        }
     }
 }
 
@@ -34,201 +71,332 @@ enum SchematicError {
     EmptyString,
 }
 
-impl FromStr for Schematic {
-    type Err = SchematicError;
+/// What a character other than a digit stands for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SymbolClass {
+    Empty,
+    Symbol,
+    Gear,
+}
+
+impl FromStr for SymbolClass {
+    type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if s.len() == 0 {
-            return Err(EmptyString);
+        match s {
+            "empty" => Ok(SymbolClass::Empty),
+            "symbol" => Ok(SymbolClass::Symbol),
+            "gear" => Ok(SymbolClass::Gear),
+            _ => Err(format!("Unknown symbol class '{s}'")),
         }
+    }
+}
 
-        let height = s.lines().filter(|str| str.trim().len() > 0).count();
-        let width = s
-            .lines()
-            .last()
-            .expect("should have at least one line")
-            .trim()
-            .len();
+/// Class of every character, characters that aren't in the map are plain symbols
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SymbolClasses(HashMap<char, SymbolClass>);
 
-        let mut schematic = Schematic {
-            width,
-            height,
-            values: vec![],
-        };
+impl Default for SymbolClasses {
+    fn default() -> Self {
+        SymbolClasses(HashMap::from([
+            ('.', SymbolClass::Empty),
+            ('*', SymbolClass::Gear),
+        ]))
+    }
+}
 
-        for line in s.lines() {
-            let mut row = vec![];
+impl SymbolClasses {
+    /// The defaults changed by `class=chars` entries, e.g. `gear=*#$`
+    fn parse<S: AsRef<str>>(entries: &[S]) -> Result<SymbolClasses, String> {
+        let mut classes = SymbolClasses::default();
 
-            for char in line.trim().chars() {
-                if char == '.' {
-                    row.push(Value::Empty);
-                    continue;
-                }
+        for entry in entries {
+            let entry = entry.as_ref();
+            let (class, chars) = entry
+                .split_once('=')
+                .ok_or_else(|| format!("Invalid class '{entry}', expected class=chars"))?;
+            let class = class.trim().parse::<SymbolClass>()?;
 
-                if char == '*' {
-                    row.push(Value::Gear);
-                    continue;
-                }
+            if let Some(digit) = chars.chars().find(char::is_ascii_digit) {
+                return Err(format!("Digit '{digit}' can't be a symbol"));
+            }
 
-                if char.is_numeric() {
-                    let val = char.to_digit(10).unwrap() as usize;
-                    row.push(Value::Digit(val));
-                    continue;
-                }
+            classes.0.extend(chars.chars().map(|c| (c, class)));
+        }
 
-                row.push(Value::Symbol);
-            }
+        Ok(classes)
+    }
 
-            schematic.values.push(row);
+    fn value(&self, c: char) -> Value {
+        if let Some(digit) = c.to_digit(10) {
+            return Value::Digit(digit as usize);
         }
 
-        Ok(schematic)
+        match self.0.get(&c).copied().unwrap_or(SymbolClass::Symbol) {
+            SymbolClass::Empty => Value::Empty,
+            SymbolClass::Symbol => Value::Symbol(c),
+            SymbolClass::Gear => Value::Gear(c),
+        }
     }
 }
 
-#[derive(Copy, Clone)]
-enum FindAdjacentValueSearchParam {
-    IsSymbol,
-    IsDigit,
+/// How many numbers a gear needs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GearCount {
+    AtLeast(usize),
+    Exactly(usize),
 }
 
-impl Schematic {
-    fn find_part_numbers(&self) -> Vec<usize> {
-        let mut numbers = vec![];
+/// How the numbers of a gear make up its ratio
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Combine {
+    Product,
+    Sum,
+    Max,
+}
 
-        for (row_index, row) in self.values.iter().enumerate() {
-            let mut digits = vec![];
+/// When a gear symbol counts as a gear and what its ratio is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct GearRule {
+    count: GearCount,
+    combine: Combine,
+}
 
-            for (column_index, value) in row.iter().enumerate() {
-                if let Value::Digit(digit) = value {
-                    let column_start = column_index - digits.len();
+impl Default for GearRule {
+    fn default() -> Self {
+        GearRule {
+            count: GearCount::AtLeast(2),
+            combine: Combine::Product,
+        }
+    }
+}
 
-                    digits.push(digit.clone());
+impl GearRule {
+    /// The ratio, None if the gear has the wrong number of numbers
+    fn ratio(&self, numbers: &[usize]) -> Option<usize> {
+        let matches = match self.count {
+            GearCount::AtLeast(count) => numbers.len() >= count,
+            GearCount::Exactly(count) => numbers.len() == count,
+        };
 
-                    if self.is_next_number(row_index, column_index) {
-                        continue;
-                    }
+        matches.then(|| match self.combine {
+            Combine::Product => numbers.iter().product(),
+            Combine::Sum => numbers.iter().sum(),
+            Combine::Max => numbers.iter().copied().max().unwrap_or(0),
+        })
+    }
+}
 
-                    // this number has no adjacent symbols so we can ignore it
-                    if !self.has_adjacent_symbol_range(row_index, column_start, column_index) {
-                        digits.clear();
-                        continue;
-                    }
+impl FromStr for GearRule {
+    type Err = String;
 
-                    numbers.push(create_number_from_digits(digits.clone()));
-                    digits.clear();
-                    continue;
-                }
-            }
-        }
+    /// `<count>[+][:product|sum|max]`, `2+` means at least two numbers and `2` exactly two
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (count, combine) = s.split_once(':').unwrap_or((s, "product"));
+
+        let invalid = || format!("Invalid gear rule '{s}', expected e.g. 2+:product");
+        // only gears next to a number are looked at, so a gear never has zero numbers
+        let parse_count = |count: &str| match count.parse() {
+            Ok(0) => Err(format!(
+                "Invalid gear rule '{s}', gears need at least one number"
+            )),
+            Ok(count) => Ok(count),
+            Err(_) => Err(invalid()),
+        };
+        let count = match count.strip_suffix('+') {
+            Some(count) => GearCount::AtLeast(parse_count(count)?),
+            None => GearCount::Exactly(parse_count(count)?),
+        };
+        let combine = match combine {
+            "product" => Combine::Product,
+            "sum" => Combine::Sum,
+            "max" => Combine::Max,
+            _ => return Err(invalid()),
+        };
 
-        numbers
+        Ok(GearRule { count, combine })
     }
+}
 
-    fn find_gear_ratios(&self) -> Vec<usize> {
-        let mut numbers = vec![];
+impl FromStr for Schematic {
+    type Err = SchematicError;
 
-        for (row_index, row) in self.values.iter().enumerate() {
-            for (column_index, value) in row.iter().enumerate() {
-                if value.clone() != Value::Gear {
-                    continue;
-                }
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Schematic::parse(s, &SymbolClasses::default())
+    }
+}
 
-                let digits = self.find_adjacent_symbols(
-                    row_index,
-                    column_index,
-                    FindAdjacentValueSearchParam::IsDigit,
-                );
+impl Schematic {
+    fn parse(s: &str, classes: &SymbolClasses) -> Result<Self, SchematicError> {
+        if s.is_empty() {
+            return Err(EmptyString);
+        }
 
-                let mut found_map = HashMap::new();
+        let height = s.lines().filter(|str| !str.trim().is_empty()).count();
+        let width = s
+            .lines()
+            .last()
+            .expect("should have at least one line")
+            .trim()
+            .len();
 
-                for (digit_row, digit_col, _) in digits.iter() {
-                    let (num, key) =
-                        self.find_number_from_digit(digit_row.clone(), digit_col.clone());
+        let mut schematic = Schematic {
+            width,
+            height,
+            values: vec![],
+        };
 
-                    if found_map.contains_key(&key) {
-                        continue;
-                    }
-                    found_map.insert(key, num);
-                }
+        for line in s.lines() {
+            let row = line.trim().chars().map(|c| classes.value(c)).collect();
+            schematic.values.push(row);
+        }
 
-                if found_map.len() < 2 {
-                    continue;
-                }
+        Ok(schematic)
+    }
+}
 
-                numbers.push(found_map.values().product());
-            }
-        }
+/// Symbol touching a number, diagonals included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct AdjacentSymbol {
+    row: usize,
+    column: usize,
+    symbol: char,
+    gear: bool,
+}
 
-        numbers
+impl AdjacentSymbol {
+    fn to_json(self) -> Json {
+        Json::object([
+            ("row", self.row.into()),
+            ("column", self.column.into()),
+            ("symbol", self.symbol.to_string().into()),
+            ("gear", self.gear.into()),
+        ])
     }
+}
 
-    fn find_number_from_digit(&self, row: usize, column: usize) -> (usize, (usize, usize, usize)) {
-        // find actual start
-        let mut col_start = column;
-        if column > 0 {
-            for col in (0..=column).rev() {
-                let value = self.get(row, col).unwrap();
+/// Run of digits in a row with the symbols around it
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Number {
+    row: usize,
+    /// first and last column, both inclusive
+    columns: (usize, usize),
+    value: usize,
+    /// in row order
+    symbols: Vec<AdjacentSymbol>,
+}
 
-                if let Value::Digit(_) = value {
-                    col_start = col;
-                    continue;
-                }
+impl Number {
+    fn is_part(&self) -> bool {
+        !self.symbols.is_empty()
+    }
 
-                break;
-            }
-        }
+    fn cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (self.columns.0..=self.columns.1).map(|column| (column, self.row))
+    }
 
-        // find actual end
-        let mut col_end = column;
-        if column < self.width {
-            for col in column..self.width {
-                let value = self.get(row, col).unwrap();
+    fn to_json(&self) -> Json {
+        Json::object([
+            ("row", self.row.into()),
+            ("start", self.columns.0.into()),
+            ("end", self.columns.1.into()),
+            ("value", self.value.into()),
+            ("part", self.is_part().into()),
+            (
+                "symbols",
+                Json::Array(self.symbols.iter().map(|s| s.to_json()).collect()),
+            ),
+        ])
+    }
+}
 
-                if let Value::Digit(_) = value {
-                    col_end = col;
-                    continue;
+impl Schematic {
+    /// Every run of digits, numbers never continue on the next row
+    fn number_spans(&self) -> Labels {
+        label(self, Connectivity::Horizontal, |value| {
+            matches!(value, Value::Digit(_))
+        })
+    }
+
+    fn span_value(&self, span: &Region) -> usize {
+        from_digits(
+            span.cells()
+                .iter()
+                .filter_map(|&(x, y)| match self.get(y, x) {
+                    Some(Value::Digit(digit)) => Some(digit),
+                    _ => None,
+                }),
+            10,
+        )
+    }
+
+    /// Every number in reading order
+    fn numbers(&self) -> Vec<Number> {
+        self.number_spans()
+            .regions()
+            .iter()
+            .map(|span| {
+                let ((start, row), (end, _)) = span.bounds();
+
+                Number {
+                    row,
+                    columns: (start, end),
+                    value: self.span_value(span),
+                    symbols: self.symbols_around(row, start, end),
                 }
+            })
+            .collect()
+    }
 
-                break;
-            }
-        }
+    // symbols in the rectangle one cell larger than the span
+    fn symbols_around(&self, row: usize, start: usize, end: usize) -> Vec<AdjacentSymbol> {
+        let rows = row.saturating_sub(1)..=(row + 1).min(self.height - 1);
+
+        rows.flat_map(|row| {
+            (start.saturating_sub(1)..=(end + 1).min(self.width - 1))
+                .map(move |column| (row, column))
+        })
+        .filter_map(|(row, column)| {
+            let value = self.get(row, column)?;
+
+            value.is_symbol().then(|| AdjacentSymbol {
+                row,
+                column,
+                symbol: value.to_char(),
+                gear: matches!(value, Value::Gear(_)),
+            })
+        })
+        .collect()
+    }
+
+    fn find_part_numbers(&self) -> Vec<usize> {
+        self.numbers()
+            .iter()
+            .filter(|number| number.is_part())
+            .map(|number| number.value)
+            .collect()
+    }
 
-        let mut digits = vec![];
-        for col in col_start..=col_end {
-            if let Value::Digit(digit) = self.get(row, col).unwrap() {
-                digits.push(digit);
+    /// Values of the numbers next to every gear, by gear position
+    fn gears(&self) -> BTreeMap<(usize, usize), Vec<usize>> {
+        let mut gears = BTreeMap::<_, Vec<_>>::new();
+
+        for number in self.numbers() {
+            for symbol in number.symbols.iter().filter(|s| s.gear) {
+                gears
+                    .entry((symbol.row, symbol.column))
+                    .or_default()
+                    .push(number.value);
             }
         }
 
-        (create_number_from_digits(digits), (row, col_start, col_end))
+        gears
     }
 
-    fn has_adjacent_symbol_range(
-        &self,
-        row: usize,
-        column_start: usize,
-        column_end: usize,
-    ) -> bool {
-        self.find_adjacent_symbols_range(
-            row,
-            column_start,
-            column_end,
-            FindAdjacentValueSearchParam::IsSymbol,
-        )
-        .len()
-            > 0
-    }
-
-    fn find_adjacent_symbols_range(
-        &self,
-        row: usize,
-        column_start: usize,
-        column_end: usize,
-        search_value: FindAdjacentValueSearchParam,
-    ) -> Vec<(usize, usize, Value)> {
-        (column_start..=column_end)
-            .flat_map(|col| self.find_adjacent_symbols(row, col, search_value.clone()))
+    fn find_gear_ratios(&self, rule: &GearRule) -> Vec<usize> {
+        self.gears()
+            .values()
+            .filter_map(|numbers| rule.ratio(numbers))
             .collect()
     }
 
@@ -238,75 +406,85 @@ impl Schematic {
             .and_then(|values_column| values_column.get(column).cloned())
     }
 
-    fn find_adjacent_symbols(
-        &self,
-        row: usize,
-        column: usize,
-        search_value: FindAdjacentValueSearchParam,
-    ) -> Vec<(usize, usize, Value)> {
-        let mut values = vec![];
-
-        let start_row = row.saturating_sub(1);
-        let end_row = (row + 1).min(self.height - 1);
-        let start_col = column.saturating_sub(1);
-        let end_col = (column + 1).min(self.width - 1);
-
-        for row_index in start_row..=end_row {
-            for col_index in start_col..=end_col {
-                let value = self.get(row_index, col_index);
-
-                if let Some(value) = value {
-                    match search_value {
-                        FindAdjacentValueSearchParam::IsSymbol => {
-                            if value.is_symbol() {
-                                values.push((row_index, col_index, value));
-                            }
-                        }
-                        FindAdjacentValueSearchParam::IsDigit => {
-                            if let Value::Digit(_) = value {
-                                values.push((row_index, col_index, value));
-                            }
-                        }
-                    };
-                }
-            }
-        }
+    /// All numbers plus both results
+    fn to_json(&self, rule: &GearRule) -> Json {
+        Json::object([
+            ("sum", self.sum().into()),
+            ("gear_part_sum", self.gear_part_sum(rule).into()),
+            (
+                "numbers",
+                Json::Array(self.numbers().iter().map(Number::to_json).collect()),
+            ),
+        ])
+    }
 
-        values
+    /// Positions of all digits that belong to a part number
+    fn part_number_cells(&self) -> HashSet<(usize, usize)> {
+        self.numbers()
+            .iter()
+            .filter(|number| number.is_part())
+            .flat_map(|number| number.cells())
+            .collect()
     }
 
-    fn is_next_number(&self, row: usize, column: usize) -> bool {
-        if column + 1 >= self.width {
-            return false;
-        }
+    /// Part numbers are green and gears red
+    fn render(&self, mode: ColorMode) -> String {
+        let parts = self.part_number_cells();
+
+        render(self, mode, |x, y, value| {
+            let style = match value {
+                Value::Empty => Style::PLAIN.dim(),
+                Value::Symbol(_) => Style::PLAIN.bold(),
+                Value::Gear(_) => Style::fg(Color::Red).bold(),
+                Value::Digit(_) if parts.contains(&(x, y)) => Style::fg(Color::Green),
+                Value::Digit(_) => Style::PLAIN,
+            };
+
+            (value.to_char(), style)
+        })
+    }
 
-        let next = self.values[row][column + 1];
+    fn image(&self, scale: usize) -> Image {
+        let parts = self.part_number_cells();
 
-        !next.is_symbol() && next != Value::Empty
+        Image::from_grid(self, scale, |x, y, value| match value {
+            Value::Empty => Rgb::BLACK,
+            Value::Symbol(_) => Rgb::WHITE,
+            Value::Gear(_) => Rgb::RED,
+            Value::Digit(_) if parts.contains(&(x, y)) => Rgb::GREEN,
+            Value::Digit(_) => Rgb::GRAY,
+        })
     }
 
     fn sum(&self) -> usize {
         self.find_part_numbers().iter().sum()
     }
 
-    fn gear_part_sum(&self) -> usize {
-        self.find_gear_ratios().iter().sum()
+    fn gear_part_sum(&self, rule: &GearRule) -> usize {
+        self.find_gear_ratios(rule).iter().sum()
     }
 }
 
-fn create_number_from_digits(digits: Vec<usize>) -> usize {
-    let mut value = 0;
+impl Grid2D for Schematic {
+    type Cell = Value;
 
-    for (index, digit) in digits.iter().rev().enumerate() {
-        value += 10_usize.pow(index as u32) * digit;
+    fn width(&self) -> usize {
+        self.width
     }
 
-    value
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        self.get(y, x)
+    }
 }
 
 #[cfg(test)]
 mod test_2023_03 {
-    use crate::{Schematic, Value};
+    use crate::{AdjacentSymbol, GearRule, Number, Schematic, SymbolClasses, Value};
+    use aoc::ansi::ColorMode;
     use std::str::FromStr;
 
     const TEST_SIMPLIFIED_SCHEMATIC: &str = "\
@@ -325,7 +503,12 @@ $..1
             // 12..
             [Value::Digit(1), Value::Digit(2), Value::Empty, Value::Empty],
             // $...
-            [Value::Symbol, Value::Empty, Value::Empty, Value::Digit(1)],
+            [
+                Value::Symbol('$'),
+                Value::Empty,
+                Value::Empty,
+                Value::Digit(1),
+            ],
             // 8...
             [Value::Digit(8), Value::Empty, Value::Empty, Value::Empty],
         ];
@@ -357,6 +540,15 @@ $..1
         assert_eq!(4361, schematic.sum());
     }
 
+    #[test]
+    fn test_render() {
+        let schematic = Schematic::from_str(TEST_SCHEMATICS).expect("should parse");
+        assert_eq!(TEST_SCHEMATICS, schematic.render(ColorMode::Never));
+
+        let colored = schematic.render(ColorMode::Always);
+        assert!(colored.starts_with("\x1b[32m467\x1b[0m\x1b[2m..\x1b[0m114"));
+    }
+
     const TEST_SCHEMATIC_GEAR_TEST: &str = "\
 467..114..
 ...*......
@@ -372,7 +564,7 @@ $..1
     #[test]
     fn test_parsing_gear_part_example() {
         let schematic = Schematic::from_str(TEST_SCHEMATIC_GEAR_TEST).expect("should parse");
-        assert_eq!(467835, schematic.gear_part_sum());
+        assert_eq!(467835, schematic.gear_part_sum(&GearRule::default()));
     }
 
     const TEST_SCHEMATIC_GEAR_EXHAUSTIVE: &str = "\
@@ -388,6 +580,105 @@ $..1
     #[test]
     fn test_parsing_gear_part_example_exhaustive() {
         let schematic = Schematic::from_str(TEST_SCHEMATIC_GEAR_EXHAUSTIVE).expect("should parse");
-        assert_eq!(1279, schematic.gear_part_sum());
+        assert_eq!(1279, schematic.gear_part_sum(&GearRule::default()));
+    }
+
+    #[test]
+    fn test_numbers() {
+        let schematic = Schematic::from_str(TEST_SCHEMATICS).expect("should parse");
+        let numbers = schematic.numbers();
+
+        assert_eq!(10, numbers.len());
+        assert_eq!(
+            Number {
+                row: 0,
+                columns: (0, 2),
+                value: 467,
+                symbols: vec![AdjacentSymbol {
+                    row: 1,
+                    column: 3,
+                    symbol: '*',
+                    gear: true,
+                }],
+            },
+            numbers[0]
+        );
+
+        // 114 and 58 aren't next to a symbol
+        assert_eq!(
+            vec![114, 58],
+            numbers
+                .iter()
+                .filter(|number| !number.is_part())
+                .map(|number| number.value)
+                .collect::<Vec<_>>()
+        );
+
+        assert_eq!(
+            vec![vec![467, 35], vec![617], vec![755, 598]],
+            schematic.gears().into_values().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_json() {
+        let schematic = Schematic::from_str(TEST_SIMPLIFIED_SCHEMATIC).expect("should parse");
+
+        assert_eq!(
+            concat!(
+                r#"{"sum":20,"gear_part_sum":0,"numbers":["#,
+                r#"{"row":0,"start":0,"end":1,"value":12,"part":true,"symbols":[{"row":1,"column":0,"symbol":"$","gear":false}]},"#,
+                r#"{"row":1,"start":3,"end":3,"value":1,"part":false,"symbols":[]},"#,
+                r#"{"row":2,"start":0,"end":0,"value":8,"part":true,"symbols":[{"row":1,"column":0,"symbol":"$","gear":false}]}]}"#
+            ),
+            schematic.to_json(&GearRule::default()).to_string()
+        );
+    }
+
+    #[test]
+    fn test_gear_rules() {
+        let schematic = Schematic::from_str(TEST_SCHEMATIC_GEAR_EXHAUSTIVE).expect("should parse");
+        let sum = |rule: &str| schematic.gear_part_sum(&rule.parse::<GearRule>().unwrap());
+
+        assert_eq!(1279, sum("2+"));
+        assert_eq!(1279, sum("2+:product"));
+        assert_eq!(16 + 4 + 22 + 1221, sum("2"));
+        assert_eq!(16, sum("4"));
+        assert_eq!(8 + 4 + 13 + 122 + 8, sum("2+:sum"));
+        assert_eq!(4 + 2 + 11 + 111 + 2, sum("1+:max"));
+
+        assert!("two".parse::<GearRule>().is_err());
+        assert!("2:min".parse::<GearRule>().is_err());
+
+        // gears without numbers never show up
+        assert!("0".parse::<GearRule>().is_err());
+        assert!("0+".parse::<GearRule>().is_err());
+        assert!("0:sum".parse::<GearRule>().is_err());
+    }
+
+    #[test]
+    fn test_symbol_classes() {
+        let input = "1#2.\n..~3\n4$..";
+        let classes = SymbolClasses::parse(&["gear=#$", "empty=~"]).unwrap();
+        let schematic = Schematic::parse(input, &classes).expect("should parse");
+
+        assert_eq!(Some(Value::Gear('#')), schematic.get(0, 1));
+        assert_eq!(Some(Value::Empty), schematic.get(1, 2));
+        assert_eq!(2, schematic.gear_part_sum(&GearRule::default()));
+        assert_eq!(1 + 2 + 4, schematic.sum());
+
+        // only '#' has two numbers, '$' has just the 4
+        assert_eq!(
+            4,
+            schematic.gear_part_sum(&"1:product".parse::<GearRule>().unwrap())
+        );
+
+        let schematic = Schematic::from_str(input).expect("should parse");
+        assert_eq!(Some(Value::Symbol('~')), schematic.get(1, 2));
+        assert_eq!(0, schematic.gear_part_sum(&GearRule::default()));
+
+        assert!(SymbolClasses::parse(&["gear"]).is_err());
+        assert!(SymbolClasses::parse(&["wheel=#"]).is_err());
+        assert!(SymbolClasses::parse(&["gear=1"]).is_err());
     }
 }
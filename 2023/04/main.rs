@@ -1,4 +1,6 @@
 use crate::ParseError::InvalidFormat;
+use aoc::answer::Answer;
+use aoc::search::{topological_sort, Graph};
 use std::collections::HashMap;
 use std::str::FromStr;
 
@@ -18,79 +20,49 @@ struct Game {
     cards: HashMap<u32, Card>,
 }
 
-fn merge_hashmap(a: &HashMap<u32, u32>, b: &HashMap<u32, u32>) -> HashMap<u32, u32> {
-    let mut new_hashmap = a.clone();
-
-    for (b_key, b_value) in b.iter() {
-        let val = new_hashmap.entry(b_key.clone()).or_insert(0);
-        *val += b_value;
-    }
-
-    new_hashmap
-}
-
 impl Game {
     fn total_ppints(&self) -> u32 {
         self.cards.values().map(|card| card.points()).sum()
     }
 
-    fn scratch_cards(&self) -> HashMap<u32, u32> {
-        let mut cache: HashMap<u32, HashMap<u32, u32>> = HashMap::new();
-        let mut cards = HashMap::new();
+    fn scratch_cards(&self) -> HashMap<u32, Answer> {
+        let order = topological_sort(self, self.cards.keys().copied())
+            .expect("cards can only win copies of later cards");
 
-        for card_id in self.cards.keys() {
-            let results = if cache.contains_key(card_id) {
-                cache.get(card_id).unwrap()
-            } else {
-                let results = self.scratch_cards_for(card_id.clone(), &mut cache);
-                cache.insert(card_id.clone(), results);
-                cache.get(card_id).unwrap()
-            };
+        // every card exists once, each copy of it wins one more copy of all its successors
+        let mut cards: HashMap<u32, Answer> =
+            self.cards.keys().map(|id| (*id, Answer::ONE)).collect();
 
-            cards = merge_hashmap(&cards, &results);
+        for id in order {
+            let copies = cards[&id].clone();
+
+            for next_id in self.successors(&id) {
+                *cards.get_mut(&next_id).unwrap() += copies.clone();
+            }
         }
 
         cards
     }
 
-    fn scratch_cards_for(
-        &self,
-        id: u32,
-        mut cache: &mut HashMap<u32, HashMap<u32, u32>>,
-    ) -> HashMap<u32, u32> {
-        let mut cards = HashMap::new();
-
-        let card = self.cards.get(&id);
-
-        if card.is_none() {
-            return cards;
-        }
-
-        let card = card.unwrap();
-
-        cards.insert(card.id, 1);
-
-        let count = card.user_winning_numbers().len();
-
-        let new_ids: Vec<u32> = (1..=count).map(|num| id + (num as u32)).collect();
+    fn scratch_cards_total(&self) -> Answer {
+        self.scratch_cards().values().sum()
+    }
+}
 
-        for new_id in new_ids.iter() {
-            let results = if cache.contains_key(new_id) {
-                cache.get(new_id).unwrap()
-            } else {
-                let results = self.scratch_cards_for(new_id.clone(), &mut cache);
-                cache.insert(new_id.clone(), results);
-                cache.get(new_id).unwrap()
-            };
+// a card wins copies of the cards following it
+impl Graph for Game {
+    type Node = u32;
 
-            cards = merge_hashmap(&cards, &results);
-        }
+    fn successors(&self, id: &u32) -> Vec<u32> {
+        let Some(card) = self.cards.get(id) else {
+            return vec![];
+        };
 
-        cards
-    }
+        let count = card.user_winning_numbers().len() as u32;
 
-    fn scratch_cards_total(&self) -> u32 {
-        self.scratch_cards().values().sum()
+        (id + 1..=id + count)
+            .filter(|id| self.cards.contains_key(id))
+            .collect()
     }
 }
 
@@ -122,7 +94,7 @@ impl Card {
         self.user_numbers
             .iter()
             .filter(|num| self.winning_numbers.contains(num))
-            .map(|num| num.clone())
+            .copied()
             .collect()
     }
 
@@ -167,7 +139,7 @@ impl FromStr for Card {
             .expect("should exist")
             .trim()
             .split(" ")
-            .filter(|num| num.trim().len() > 0)
+            .filter(|num| !num.trim().is_empty())
             .map(|num| num.parse().expect("should be a number"))
             .collect();
         let user_numbers: Vec<u32> = parts
@@ -175,7 +147,7 @@ impl FromStr for Card {
             .expect("should exist")
             .trim()
             .split(" ")
-            .filter(|num| num.trim().len() > 0)
+            .filter(|num| !num.trim().is_empty())
             .map(|num| num.parse().expect("should be a number"))
             .collect();
 
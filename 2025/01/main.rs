@@ -1,3 +1,7 @@
+use aoc::modint::ModInt;
+use aoc::sim::{Driver, Simulation};
+use std::slice;
+
 fn main() {
     let input = include_str!("./input.txt");
     let rots = parse_rot_seq(input);
@@ -45,71 +49,91 @@ pub fn parse_rot_seq(input: &str) -> Vec<Rot> {
     input.lines().map(|line| line.to_string().into()).collect()
 }
 
+pub type Dial = ModInt<100>;
+
+#[derive(Debug, Clone, Copy)]
 pub struct Safe {
-    pub current: u8,
+    pub dial: Dial,
     pub clicks: u32,
 }
 
 impl Safe {
-    pub fn new(current: u8) -> Self {
-        Safe { current, clicks: 0 }
-    }
-
-    pub fn with_clicks(self, clicks: u32) -> Safe {
+    pub fn new(current: u64) -> Self {
         Safe {
-            current: self.current,
-            clicks,
+            dial: Dial::new(current),
+            clicks: 0,
         }
     }
 
+    pub fn current(&self) -> u64 {
+        self.dial.value()
+    }
+
     pub fn apply_rotation(self, rot: Rot) -> Self {
         match rot {
-            Rot::Left(val) => self.turn(-i16::from(val)),
-            Rot::Right(val) => self.turn(i16::from(val)),
+            Rot::Left(val) => self.turn(-val),
+            Rot::Right(val) => self.turn(val),
         }
     }
 
-    pub fn apply_rotations(self, rots: &Vec<Rot>) -> Self {
-        rots.iter()
-            .cloned()
-            .fold(self, |safe, rot| safe.apply_rotation(rot))
+    pub fn apply_rotations(self, rots: &[Rot]) -> Self {
+        let mut sim = Rotations::new(self, rots);
+        Driver::new().run(&mut sim);
+
+        sim.safe
     }
 
     fn turn(self, val: i16) -> Self {
-        let mut current = self.current as i16;
-        let mut clicks = self.clicks;
+        let (dial, clicks) = self.dial.add_counting(i64::from(val), 0);
 
-        let dir = if val >= 0 { 1 } else { -1 };
+        Safe {
+            dial,
+            clicks: self.clicks + clicks as u32,
+        }
+    }
+}
 
-        for _ in 0..val.abs() {
-            current = (current + dir).rem_euclid(100);
+/// Applies one rotation per step
+pub struct Rotations<'a> {
+    pub safe: Safe,
+    rots: slice::Iter<'a, Rot>,
+}
 
-            if current == 0 {
-                clicks += 1;
-            }
+impl<'a> Rotations<'a> {
+    pub fn new(safe: Safe, rots: &'a [Rot]) -> Self {
+        Rotations {
+            safe,
+            rots: rots.iter(),
         }
+    }
+}
 
-        Safe::new(u8::try_from(current).expect("invalid state")).with_clicks(clicks)
+impl Simulation for Rotations<'_> {
+    fn step(&mut self) -> bool {
+        let Some(&rot) = self.rots.next() else {
+            return false;
+        };
+
+        self.safe = self.safe.apply_rotation(rot);
+        true
     }
 }
 
-fn count_state_reached(safe: Safe, rots: &Vec<Rot>, value: u8) -> u32 {
-    rots.iter()
-        .cloned()
-        .fold((safe, 0), |(safe, curr), rot| {
-            let safe = safe.apply_rotation(rot);
-            let v = safe.current;
+fn count_state_reached(safe: Safe, rots: &[Rot], value: u64) -> u32 {
+    let mut count = 0;
+
+    Driver::new()
+        .observe(|_, sim: &Rotations| count += u32::from(sim.safe.current() == value))
+        .run(&mut Rotations::new(safe, rots));
 
-            (safe, if v == value { curr + 1 } else { curr })
-        })
-        .1
+    count
 }
 
 #[cfg(test)]
 mod test {
     use crate::{count_state_reached, parse_rot_seq, Rot, Safe};
 
-    const DEMO_INPUT: &'static str = r#"L68
+    const DEMO_INPUT: &str = r#"L68
 L30
 R48
 L5
@@ -149,19 +173,19 @@ L82"#;
 
     #[test]
     fn test_safe_apply_rotations() {
-        assert_eq!(19, Safe::new(11).apply_rotation(Rot::Right(8)).current);
-        assert_eq!(5, Safe::new(11).apply_rotation(Rot::Left(6)).current);
+        assert_eq!(19, Safe::new(11).apply_rotation(Rot::Right(8)).current());
+        assert_eq!(5, Safe::new(11).apply_rotation(Rot::Left(6)).current());
         assert_eq!(
             0,
             Safe::new(11)
                 .apply_rotation(Rot::Right(8))
                 .apply_rotation(Rot::Left(19))
-                .current
+                .current()
         );
-        assert_eq!(99, Safe::new(0).apply_rotation(Rot::Left(1)).current);
-        assert_eq!(0, Safe::new(99).apply_rotation(Rot::Right(1)).current);
-        assert_eq!(50, Safe::new(50).apply_rotation(Rot::Left(0)).current);
-        assert_eq!(50, Safe::new(50).apply_rotation(Rot::Right(0)).current);
+        assert_eq!(99, Safe::new(0).apply_rotation(Rot::Left(1)).current());
+        assert_eq!(0, Safe::new(99).apply_rotation(Rot::Right(1)).current());
+        assert_eq!(50, Safe::new(50).apply_rotation(Rot::Left(0)).current());
+        assert_eq!(50, Safe::new(50).apply_rotation(Rot::Right(0)).current());
     }
 
     #[test]
@@ -179,7 +203,7 @@ L82"#;
     #[test]
     fn test_big_rotation_clicks() {
         let res = Safe::new(50).apply_rotation(Rot::Right(1000));
-        assert_eq!(50, res.current);
+        assert_eq!(50, res.current());
         assert_eq!(10, res.clicks);
     }
 }
@@ -1,3 +1,5 @@
+use aoc::digits;
+
 fn main() {
     let input = include_str!("./input.txt");
     println!(
@@ -10,72 +12,15 @@ fn main() {
     );
 }
 
-fn num_digits(n: &u64) -> usize {
-    (n.checked_ilog10().unwrap_or(0) + 1) as usize
-}
-
-fn split_num(n: &u64, parts: usize) -> Option<Vec<u64>> {
-    // no parts? Drop it
-    if parts == 0 {
-        return None;
-    }
-
-    let digits = num_digits(n);
-
-    // if isnt divisible cleanly, drop it
-    if digits % parts != 0 {
-        return None;
-    }
-
-    let chunk = digits / parts;
-    let div = 10u64.pow(chunk as u32);
-    let mut res = Vec::with_capacity(parts);
-    let mut x = n.clone();
-
-    for _ in 1..parts {
-        res.push(x % div);
-        x /= div;
-    }
-
-    res.push(x);
-
-    Some(res)
-}
-
-fn power_split(n: &u64) -> Vec<Vec<u64>> {
-    let digits = num_digits(n);
-    let mut res = Vec::new();
-
-    for parts in 1..=digits {
-        if digits % parts != 0 {
-            continue;
-        }
-
-        if let Some(split) = split_num(&n, parts) {
-            res.push(split);
-        }
-    }
-
-    res
-}
-
 fn find_invalid_ids_pair(from: u64, to: u64) -> Vec<u64> {
     (from..=to)
-        .filter(|num| match split_num(num, 2).as_deref() {
-            Some([left, right]) => left == right,
-            _ => false,
-        })
+        .filter(|num| digits::is_repeated(*num, 2, 10))
         .collect()
 }
 
 fn find_invalid_ids_multiple(from: u64, to: u64) -> Vec<u64> {
     (from..=to)
-        .filter(|num| {
-            power_split(num)
-                .iter()
-                .filter(|v| v.len() >= 2)
-                .any(|v| v.windows(2).all(|w| w[0] == w[1]))
-        })
+        .filter(|num| digits::has_repeated_pattern(*num, 10))
         .collect()
 }
 
@@ -91,18 +36,14 @@ fn sum_invalid_ids<F>(ranges: Vec<(u64, u64)>, f: F) -> u64
 where
     F: Fn(u64, u64) -> Vec<u64>,
 {
-    ranges
-        .iter()
-        .map(|(from, to)| f(from.clone(), to.clone()))
-        .flatten()
-        .fold(0, |acc, id| acc + id)
+    ranges.iter().flat_map(|(from, to)| f(*from, *to)).sum()
 }
 
 #[cfg(test)]
 mod test {
     use crate::{find_invalid_ids_multiple, find_invalid_ids_pair, parse_input, sum_invalid_ids};
 
-    const DEMO_INPUT: &'static str = r#"
+    const DEMO_INPUT: &str = r#"
 11-22,95-115,998-1012,1188511880-1188511890,222220-222224,
 1698522-1698528,446443-446449,38593856-38593862,565653-565659,
 824824821-824824827,2121212118-2121212124
@@ -1,3 +1,5 @@
+use aoc::answer::Answer;
+
 fn main() {
     let input = include_str!("./input.txt");
 
@@ -30,14 +32,14 @@ fn find_biggest_index_with_room(nums: &[u8], window: usize) -> usize {
     biggest_idx
 }
 
-fn find_largest_voltage(batteries: &Vec<u8>, window: usize) -> u64 {
+fn find_largest_voltage(batteries: &[u8], window: usize) -> Answer {
     let n = batteries.len();
 
     if window == 0 || window > n {
-        return 0;
+        return Answer::ZERO;
     }
 
-    let mut sum = 0;
+    let mut digits = Vec::with_capacity(window);
     let mut last_index = 0;
 
     for window_size in (1..=window).rev() {
@@ -47,16 +49,19 @@ fn find_largest_voltage(batteries: &Vec<u8>, window: usize) -> u64 {
 
         last_index = biggest_index + 1;
 
-        sum = sum * 10 + *biggest_num as u64;
+        digits.push(*biggest_num);
     }
 
-    sum
+    // windows above 19 digits don't fit into an u64 anymore
+    digits
+        .into_iter()
+        .fold(Answer::ZERO, |acc, digit| acc * 10 + u64::from(digit))
 }
 
-fn largest_voltage_sum(rows: Vec<Vec<u8>>, window: usize) -> u64 {
+fn largest_voltage_sum(rows: Vec<Vec<u8>>, window: usize) -> Answer {
     rows.iter()
         .map(|row| find_largest_voltage(row, window))
-        .fold(0, |acc, v| acc + v)
+        .sum()
 }
 
 fn parse_line(line: &str) -> Vec<u8> {
@@ -67,14 +72,14 @@ fn parse_line(line: &str) -> Vec<u8> {
 }
 
 fn parse_lines(lines: &str) -> Vec<Vec<u8>> {
-    lines.trim().lines().map(|line| parse_line(line)).collect()
+    lines.trim().lines().map(parse_line).collect()
 }
 
 #[cfg(test)]
 mod test {
     use crate::{find_largest_voltage, largest_voltage_sum, parse_lines};
 
-    const DEMO_INPUT: &'static str = r#"987654321111111
+    const DEMO_INPUT: &str = r#"987654321111111
 811111111111119
 234234234234278
 818181911112111"#;
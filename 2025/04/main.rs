@@ -1,3 +1,13 @@
+use aoc::ansi::{render, Color, ColorMode, Style};
+use aoc::args;
+use aoc::bitgrid::BitGrid;
+use aoc::grid::{Connectivity, Grid2D};
+use aoc::image::{Image, Rgb};
+use aoc::record::record;
+use aoc::region::{label, Labels};
+use aoc::sim::{Driver, Simulation};
+use aoc::tui::{self, Stepper};
+
 const ACCESSIBLE_THRESHOLD: usize = 4;
 
 fn main() {
@@ -5,147 +15,167 @@ fn main() {
     let mut g = Grid::from(input);
 
     println!("Part 1 result is {}", g.num_accessible());
+
+    if args::flag("--render") {
+        println!("{}", g.render(ColorMode::Auto));
+    }
+
+    if args::flag("--regions") {
+        let clusters = g.clusters();
+        println!("{} clusters of rolls", clusters.len());
+
+        if let Some(largest) = clusters.regions().iter().max_by_key(|r| r.area()) {
+            let ((x0, y0), (x1, y1)) = largest.bounds();
+            println!(
+                "the largest has {} rolls with a perimeter of {}, spanning ({x0}, {y0}) to ({x1}, {y1})",
+                largest.area(),
+                largest.perimeter()
+            );
+        }
+    }
+
+    if let Some(path) = args::value("--export") {
+        let scale = args::parsed("--scale", 4);
+        g.image(scale).save(&path).expect("should write the image");
+    }
+
+    if let Some(path) = args::value("--record") {
+        let scale = args::parsed("--scale", 4);
+        let mut g = Grid::from(input);
+
+        record(
+            &mut g,
+            |g| g.image(scale),
+            args::parsed("--fps", 10),
+            usize::MAX,
+        )
+        .save(&path)
+        .expect("should write the recording");
+    }
+
+    if args::flag("--tui") {
+        tui::run(Grid::from(input), args::parsed("--fps", 10)).expect("should run the stepper");
+    }
+
     println!("Part 2 result is {}", g.remove_all_accessible_with_count());
 }
 
+#[derive(Clone, PartialEq, Eq, Hash)]
 struct Grid {
-    data: Vec<bool>,
-    width: usize,
+    rolls: BitGrid,
 }
 
 impl Grid {
-    fn height(&self) -> usize {
-        (self.data.len() + self.width - 1) / self.width
-    }
+    fn accessible(&self) -> BitGrid {
+        let uncrowded = self
+            .rolls
+            .neighbor_counts(Connectivity::Eight)
+            .less_than(ACCESSIBLE_THRESHOLD);
 
-    fn index(&self, x: usize, y: usize) -> usize {
-        y * self.width + x
+        &self.rolls & &uncrowded
     }
 
-    fn xy_from_index(&self, index: usize) -> (usize, usize) {
-        (index % self.width, index / self.width)
+    fn num_accessible(&self) -> usize {
+        self.accessible().count_ones()
     }
 
-    fn get(&self, x: usize, y: usize) -> bool {
-        self.data
-            .get(self.index(x, y))
-            .expect(format!("{x}x{y} isn't accessible").as_str())
-            .clone()
-    }
+    /// Accessible rolls are highlighted
+    fn render(&self, mode: ColorMode) -> String {
+        let accessible = self.accessible();
 
-    fn remove(&mut self, x: usize, y: usize) {
-        let index = self.index(x, y);
-        self.data[index] = false;
+        render(&self.rolls, mode, |x, y, roll| {
+            match (roll, accessible.get(x, y)) {
+                (true, true) => ('x', Style::fg(Color::Green).bold()),
+                (true, false) => ('@', Style::PLAIN),
+                (false, _) => ('.', Style::PLAIN.dim()),
+            }
+        })
     }
 
-    fn num_adjacent_rolls(&self, x: usize, y: usize) -> usize {
-        let x = x as i32;
-        let y = y as i32;
+    fn image(&self, scale: usize) -> Image {
+        let accessible = self.accessible();
 
-        let mut count = 0;
-
-        for i in -1..=1 {
-            for j in -1..=1 {
-                // ignore same
-                if i == 0 && j == 0 {
-                    continue;
-                }
+        Image::from_grid(&self.rolls, scale, |x, y, roll| {
+            match (roll, accessible.get(x, y)) {
+                (true, true) => Rgb::GREEN,
+                (true, false) => Rgb::GRAY,
+                (false, _) => Rgb::BLACK,
+            }
+        })
+    }
 
-                let Some(x) = x.checked_add(i) else {
-                    continue;
-                };
+    /// Groups of rolls touching each other, diagonals included
+    fn clusters(&self) -> Labels {
+        label(&self.rolls, Connectivity::Eight, |roll| *roll)
+    }
 
-                let Some(y) = y.checked_add(j) else {
-                    continue;
-                };
+    /// Removes all accessible rolls at once and returns how many there were
+    fn remove_accessible(&mut self) -> usize {
+        let accessible = self.accessible();
+        self.rolls ^= &accessible;
 
-                if x < 0 || x >= self.width as i32 || y < 0 || y >= self.height() as i32 {
-                    continue;
-                }
+        accessible.count_ones()
+    }
 
-                if !self.get(x as usize, y as usize) {
-                    continue;
-                }
+    fn remove_all_accessible_with_count(&mut self) -> usize {
+        let before = self.rolls.count_ones();
+        Driver::new().run(self);
 
-                count += 1;
-            }
-        }
+        before - self.rolls.count_ones()
+    }
+}
 
-        count
+// every step removes all currently accessible rolls
+impl Simulation for Grid {
+    fn step(&mut self) -> bool {
+        self.remove_accessible() > 0
     }
+}
 
-    fn num_accessible(&self) -> usize {
-        self.data
-            .iter()
-            .enumerate()
-            .filter(|(_, roll)| **roll)
-            .fold(0, |acc, (index, _)| {
-                let (x, y) = self.xy_from_index(index);
-
-                if self.num_adjacent_rolls(x, y) < ACCESSIBLE_THRESHOLD {
-                    acc + 1
-                } else {
-                    acc
-                }
-            })
-    }
-
-    fn remove_accessible(&mut self) {
-        let coords = self
-            .data
-            .iter()
-            .enumerate()
-            .filter(|(_, roll)| **roll)
-            .filter_map(|(index, _)| {
-                let (x, y) = self.xy_from_index(index);
-
-                if self.num_adjacent_rolls(x, y) < ACCESSIBLE_THRESHOLD {
-                    return Some((x, y));
-                }
-
-                None
-            })
-            .collect::<Vec<(usize, usize)>>();
-
-        for (x, y) in coords {
-            self.remove(x, y);
-        }
+impl Stepper for Grid {
+    fn view(&self, mode: ColorMode) -> String {
+        self.render(mode)
     }
 
-    fn remove_all_accessible_with_count(&mut self) -> usize {
-        let mut count = 0;
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("rolls", self.rolls.count_ones().to_string()),
+            ("accessible", self.num_accessible().to_string()),
+        ]
+    }
+}
 
-        loop {
-            let num_accessible = self.num_accessible();
+impl Grid2D for Grid {
+    type Cell = bool;
 
-            if num_accessible == 0 {
-                break;
-            }
+    fn width(&self) -> usize {
+        self.rolls.width()
+    }
 
-            count += num_accessible;
-            self.remove_accessible();
-        }
+    fn height(&self) -> usize {
+        self.rolls.height()
+    }
 
-        count
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        self.rolls.cell(x, y)
     }
 }
 
 impl<'a> From<&'a str> for Grid {
     fn from(value: &'a str) -> Self {
-        let value = value.trim();
-        let width = value.find('\n').unwrap_or(value.len());
+        let lines = value
+            .trim()
+            .lines()
+            .map(|line| line.trim_end().as_bytes())
+            .collect::<Vec<_>>();
+        let width = lines.first().map_or(0, |line| line.len());
 
         Grid {
-            data: value
-                .chars()
-                .filter(|c| *c == '.' || *c == '@')
-                .map(|c| match c {
-                    '@' => true,
-                    '.' => false,
-                    _ => unreachable!(),
-                })
-                .collect(),
-            width,
+            rolls: BitGrid::from_fn(width, lines.len(), |x, y| match lines[y][x] {
+                b'@' => true,
+                b'.' => false,
+                c => panic!("unexpected character {}", c as char),
+            }),
         }
     }
 }
@@ -153,8 +183,12 @@ impl<'a> From<&'a str> for Grid {
 #[cfg(test)]
 mod test {
     use crate::Grid;
+    use aoc::ansi::ColorMode;
+    use aoc::cycle::{find_cycle, nth_state, Cycle};
+    use aoc::grid::{Connectivity, Grid2D};
+    use aoc::transform::{to_text, Rotation, TextGrid, View};
 
-    const DEMO_INPUT: &'static str = r#"
+    const DEMO_INPUT: &str = r#"
 ..@@.@@@@.
 @@@.@.@.@@
 @@@@@.@.@@
@@ -169,17 +203,17 @@ mod test {
     #[test]
     fn test_parse_input() {
         let g = Grid::from(DEMO_INPUT);
-        assert_eq!(10, g.width);
-        assert_eq!(false, g.get(0, 0));
-        assert_eq!(true, g.get(2, 0));
+        assert_eq!(10, g.rolls.width());
+        assert!(!g.rolls.get(0, 0));
+        assert!(g.rolls.get(2, 0));
     }
 
     #[test]
     fn test_num_adjacent_rolls() {
         let g = Grid::from(DEMO_INPUT);
-        assert_eq!(2, g.num_adjacent_rolls(0, 0));
-        assert_eq!(4, g.num_adjacent_rolls(1, 0));
-        assert_eq!(1, g.num_adjacent_rolls(0, 9));
+        assert_eq!(2, g.rolls.neighbor_count(0, 0, Connectivity::Eight));
+        assert_eq!(4, g.rolls.neighbor_count(1, 0, Connectivity::Eight));
+        assert_eq!(1, g.rolls.neighbor_count(0, 9, Connectivity::Eight));
     }
 
     #[test]
@@ -188,6 +222,55 @@ mod test {
         assert_eq!(13, g.num_accessible());
     }
 
+    #[test]
+    fn test_rotated() {
+        let g = Grid::from(DEMO_INPUT);
+        let text = to_text(&View::new(TextGrid::new(DEMO_INPUT.trim())).rotate(Rotation::R90));
+        let rotated = Grid::from(text.as_str());
+
+        // accessibility doesn't depend on the orientation
+        assert_eq!(13, rotated.num_accessible());
+
+        let view = View::new(&g).rotate(Rotation::R90);
+        assert!((0..10).all(|y| (0..10).all(|x| view.cell(x, y) == rotated.cell(x, y))));
+        assert_eq!(Some(true), view.cell(9, 2));
+    }
+
+    #[test]
+    fn test_clusters() {
+        let g = Grid::from("@@..@\n....@\n.@...\n@@.@@");
+        let clusters = g.clusters();
+
+        assert_eq!(4, clusters.len());
+        assert_eq!(
+            vec![2, 2, 3, 2],
+            clusters
+                .regions()
+                .iter()
+                .map(|r| r.area())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(clusters.label_at(1, 2), clusters.label_at(0, 3));
+    }
+
+    #[test]
+    fn test_render() {
+        let g = Grid::from(DEMO_INPUT);
+        let expected = r#"
+..xx.xx@x.
+x@@.@.@.@@
+@@@@@.x.@@
+@.@@@@..@.
+x@.@@@@.@x
+.@@@@@@@.@
+.@.@.@.@@@
+x.@@@.@@@@
+.@@@@@@@@.
+x.x.@@@.x."#;
+
+        assert_eq!(expected.trim(), g.render(ColorMode::Never));
+    }
+
     #[test]
     fn test_remove_accessible() {
         let mut g = Grid::from(DEMO_INPUT);
@@ -227,4 +310,28 @@ mod test {
         let mut g = Grid::from(DEMO_INPUT);
         assert_eq!(43, g.remove_all_accessible_with_count());
     }
+
+    #[test]
+    fn test_remove_accessible_fixed_point() {
+        let remove = |g: &Grid| {
+            let mut g = g.clone();
+            g.remove_accessible();
+            g
+        };
+
+        assert_eq!(
+            Cycle {
+                start: 9,
+                length: 1
+            },
+            find_cycle(Grid::from(DEMO_INPUT), remove)
+        );
+
+        let g = nth_state(Grid::from(DEMO_INPUT), remove, 1_000_000_000_000);
+        assert_eq!(0, g.num_accessible());
+        assert_eq!(
+            Grid::from(DEMO_INPUT).rolls.count_ones() - 43,
+            g.rolls.count_ones()
+        );
+    }
 }
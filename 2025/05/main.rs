@@ -1,3 +1,5 @@
+use aoc::blocks::blocks;
+
 fn main() {
     let input = include_str!("./input.txt");
 
@@ -15,7 +17,7 @@ struct Inventory {
 impl Inventory {
     fn is_fresh(&self, num: &u64) -> bool {
         for (from, to) in &self.fresh {
-            if (from..=to).contains(&&num) {
+            if (from..=to).contains(&num) {
                 return true;
             }
         }
@@ -26,16 +28,13 @@ impl Inventory {
     fn count_still_fresh_ingredients(&self) -> usize {
         self.ingredients
             .iter()
-            .filter(|num| self.is_fresh(*num))
+            .filter(|num| self.is_fresh(num))
             .count()
     }
 
     fn count_fresh(self) -> u64 {
         let fresh = merge_ranges(self.fresh);
-        fresh
-            .iter()
-            .map(|(from, to)| to - from + 1)
-            .fold(0, |acc, n| acc + n)
+        fresh.iter().map(|(from, to)| to - from + 1).sum()
     }
 }
 
@@ -52,33 +51,21 @@ fn merge_ranges(mut ranges: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
         })
 }
 
-fn split_at_empty_line(input: &str) -> (&str, &str) {
-    let bytes = input.as_bytes();
-
-    for i in 1..bytes.len() {
-        if bytes[i] == b'\n' && bytes[i - 1] == b'\n' {
-            return (&input[..i], &input[i..]);
-        }
-    }
-
-    (input, "")
-}
-
 impl<'a> From<&'a str> for Inventory {
     fn from(value: &'a str) -> Self {
-        let (fresh, available) = split_at_empty_line(value.trim());
+        let [fresh, available] = blocks::<2>(value).expect("should have ranges and ingredients");
 
         let mut inv = Inventory {
             fresh: Vec::with_capacity(fresh.len()),
             ingredients: Vec::with_capacity(available.len()),
         };
 
-        for line in fresh.trim().lines() {
+        for line in fresh.lines() {
             let (from, to) = line.split_once("-").unwrap();
             inv.fresh.push((from.parse().unwrap(), to.parse().unwrap()));
         }
 
-        for line in available.trim().lines() {
+        for line in available.lines() {
             inv.ingredients.push(line.parse().unwrap());
         }
 
@@ -90,7 +77,7 @@ impl<'a> From<&'a str> for Inventory {
 mod test {
     use crate::Inventory;
 
-    const DEMO_INPUT: &'static str = r#"
+    const DEMO_INPUT: &str = r#"
 3-5
 10-14
 16-20
@@ -1,3 +1,8 @@
+use aoc::answer::Answer;
+use aoc::digits::{digits, from_digits, num_digits};
+use aoc::grid::{FlatGrid, Grid2D};
+use aoc::transform::{Rotation, View};
+
 fn main() {
     let input = include_str!("./input.txt");
 
@@ -32,8 +37,8 @@ enum Num {
 impl Num {
     fn value(&self) -> u64 {
         match self {
-            Num::Left(v) => v.clone(),
-            Num::Right(v) => v.clone(),
+            Num::Left(v) => *v,
+            Num::Right(v) => *v,
         }
     }
 }
@@ -56,7 +61,7 @@ fn parse_sheet(rows: &str) -> (usize, Vec<Num>, Vec<Op>) {
         .iter()
         .map(|s| {
             s.split(' ')
-                .filter(|s| s.len() > 0)
+                .filter(|s| !s.is_empty())
                 .map(|s| s.len())
                 .collect::<Vec<usize>>()
         })
@@ -90,39 +95,39 @@ fn parse_sheet(rows: &str) -> (usize, Vec<Num>, Vec<Op>) {
 fn cephalon_col(col: Vec<Num>) -> Vec<u64> {
     let width = col
         .iter()
-        .map(|n| n.value().to_string().len())
+        .map(|n| num_digits(n.value(), 10))
         .max()
         .unwrap_or(1);
 
-    let lines = col
-        .iter()
-        .map(|n| match n {
-            Num::Left(n) => format!("{:<width$}", n),
-            Num::Right(n) => format!("{:>width$}", n),
-        })
-        .collect::<Vec<String>>();
-
-    let mut numbers = Vec::new();
+    // lay the column out as a grid of digits, reading it sideways starts with its rightmost
+    // digits. shorter numbers leave gaps, which are skipped.
+    let mut cells = vec![None; width * col.len()];
 
-    for i in 0..width {
-        let mut num = 0;
+    for (y, n) in col.iter().enumerate() {
+        let digits = digits(n.value(), 10).collect::<Vec<u64>>();
+        let offset = match n {
+            Num::Left(_) => 0,
+            Num::Right(_) => width - digits.len(),
+        };
 
-        for line in &lines {
-            let Ok(n) = line[i..i + 1].parse::<u64>() else {
-                continue;
-            };
-
-            num = num * 10 + n;
+        for (x, digit) in digits.into_iter().enumerate() {
+            cells[y * width + offset + x] = Some(digit);
         }
-
-        numbers.push(num);
     }
 
-    numbers.reverse();
-    numbers
+    let rotated = View::new(FlatGrid::new(&cells, width)).rotate(Rotation::R270);
+
+    (0..rotated.height())
+        .map(|y| {
+            from_digits(
+                (0..rotated.width()).filter_map(|x| rotated.cell(x, y).flatten()),
+                10,
+            )
+        })
+        .collect()
 }
 
-fn evaluate_sheet(sheet: &str) -> u64 {
+fn evaluate_sheet(sheet: &str) -> Answer {
     let (width, num_rows, ops_row) = parse_sheet(sheet);
     let height = num_rows.len() / width;
 
@@ -138,8 +143,8 @@ fn evaluate_sheet(sheet: &str) -> u64 {
                 .map(|row| num_rows.get(make_index(col, row)).unwrap())
                 .fold(
                     match op {
-                        Op::Plus => 0,
-                        Op::Mul => 1,
+                        Op::Plus => Answer::ZERO,
+                        Op::Mul => Answer::ONE,
                     },
                     |acc, val| match op {
                         Op::Plus => acc + val.value(),
@@ -149,10 +154,10 @@ fn evaluate_sheet(sheet: &str) -> u64 {
         );
     }
 
-    res.iter().fold(0, |acc, val| acc + val)
+    res.into_iter().sum()
 }
 
-fn evaluate_sheet_cephalon(sheet: &str) -> u64 {
+fn evaluate_sheet_cephalon(sheet: &str) -> Answer {
     let (width, num_rows, ops_row) = parse_sheet(sheet);
     let height = num_rows.len() / width;
 
@@ -170,24 +175,24 @@ fn evaluate_sheet_cephalon(sheet: &str) -> u64 {
 
         res.push(cephalon_col(col_numbers).iter().fold(
             match op {
-                Op::Plus => 0,
-                Op::Mul => 1,
+                Op::Plus => Answer::ZERO,
+                Op::Mul => Answer::ONE,
             },
             |acc, val| match op {
-                Op::Plus => acc + val,
-                Op::Mul => acc * val,
+                Op::Plus => acc + *val,
+                Op::Mul => acc * *val,
             },
         ));
     }
 
-    res.iter().fold(0, |acc, val| acc + val)
+    res.into_iter().sum()
 }
 
 #[cfg(test)]
 mod test {
     use crate::{cephalon_col, evaluate_sheet, evaluate_sheet_cephalon, parse_sheet, Num, Op};
 
-    const DEMO_INPUT: &'static str = r#"
+    const DEMO_INPUT: &str = r#"
 123 328  51 64
  45 64  387 23
   6 98  215 314
@@ -243,6 +248,24 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_cephalon_col_gaps() {
+        // the middle column reads 2, gap, 5, 7
+        assert_eq!(
+            vec![8, 257, 1346],
+            cephalon_col(vec![
+                Num::Left(12),
+                Num::Left(3),
+                Num::Left(45),
+                Num::Left(678)
+            ])
+        );
+        assert_eq!(
+            8 + 257 + 1346 + 1234,
+            evaluate_sheet_cephalon("12  1\n3   2\n45  3\n678 4\n+   +")
+        );
+    }
+
     #[test]
     fn test_evaluate_sheet_cephalon() {
         assert_eq!(3263827, evaluate_sheet_cephalon(DEMO_INPUT));
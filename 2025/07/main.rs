@@ -1,13 +1,54 @@
-use std::collections::{HashMap, HashSet};
+use aoc::ansi::{render, Color, ColorMode, Style};
+use aoc::answer::Answer;
+use aoc::args;
+use aoc::grid::{FlatGrid, Grid2D};
+use aoc::image::{Image, Rgb};
+use aoc::ray::{Beams, Dir, Hit, Ray};
+use aoc::record::record;
+use aoc::search::{count_paths, Graph};
+use aoc::sim::{Driver, Simulation, Stats};
+use aoc::tui::{self, Stepper};
+use std::fmt;
 
 fn main() {
     let input = include_str!("./input.txt");
 
     let mut m = Machine::from(input);
-    m.finish();
+    let stats = m.finish();
 
     println!("Part 1 result is {}", m.num_splits);
 
+    if args::flag("--stats") {
+        eprintln!("{stats}");
+    }
+
+    if args::flag("--render") {
+        println!("{}", m.render(ColorMode::Auto));
+    }
+
+    if let Some(path) = args::value("--export") {
+        let scale = args::parsed("--scale", 4);
+        m.image(scale).save(&path).expect("should write the image");
+    }
+
+    if let Some(path) = args::value("--record") {
+        let scale = args::parsed("--scale", 4);
+        let mut m = Machine::from(input);
+
+        record(
+            &mut m,
+            |m| m.image(scale),
+            args::parsed("--fps", 10),
+            usize::MAX,
+        )
+        .save(&path)
+        .expect("should write the recording");
+    }
+
+    if args::flag("--tui") {
+        tui::run(Machine::from(input), args::parsed("--fps", 10)).expect("should run the stepper");
+    }
+
     let m = Machine::from(input);
 
     println!("Part 2 result is {}", m.finish_timelines());
@@ -36,6 +77,24 @@ impl TryFrom<char> for Cell {
 }
 
 impl Cell {
+    fn style(&self) -> Style {
+        match self {
+            Cell::Start => Style::fg(Color::Green).bold(),
+            Cell::Empty => Style::PLAIN.dim(),
+            Cell::Splitter => Style::PLAIN,
+            Cell::Beam => Style::fg(Color::Yellow),
+        }
+    }
+
+    fn color(&self) -> Rgb {
+        match self {
+            Cell::Start => Rgb::GREEN,
+            Cell::Empty => Rgb::BLACK,
+            Cell::Splitter => Rgb::WHITE,
+            Cell::Beam => Rgb::YELLOW,
+        }
+    }
+
     fn to_char(&self) -> char {
         match self {
             Cell::Start => 'S',
@@ -50,14 +109,8 @@ impl Cell {
 struct Machine {
     grid: Vec<Cell>,
     width: usize,
-    beams: HashSet<(usize, usize)>,
-    num_splits: usize,
-}
-
-#[derive(Debug)]
-struct BeamResult {
-    beams: HashSet<(usize, usize)>,
-    split_beams: HashSet<(usize, usize)>,
+    start: (usize, usize),
+    beams: Beams,
     num_splits: usize,
 }
 
@@ -77,13 +130,13 @@ impl<'a> From<&'a str> for Machine {
             .map(|(index, _)| index)
             .unwrap();
 
-        let mut beams = HashSet::new();
-        beams.insert(xy_from_index(width, start_index));
+        let start = xy_from_index(width, start_index);
 
         Self {
             grid,
             width,
-            beams,
+            start,
+            beams: Beams::new(Ray::new(start, Dir::Down)),
             num_splits: 0,
         }
     }
@@ -97,139 +150,133 @@ impl Machine {
 
     #[inline]
     fn height(&self) -> usize {
-        (self.grid.len() + self.width - 1) / self.width
+        self.grid.len().div_ceil(self.width)
     }
 
-    fn advance_beams(&self) -> BeamResult {
-        let mut beams = HashSet::new();
-        let mut split_beams = HashSet::new();
-        let mut num_splits = 0;
-
-        for &(beam_x, beam_y) in &self.beams {
-            if beam_y + 1 >= self.height() {
-                continue;
-            }
-
-            let new_y = beam_y + 1;
-            let below = self.index(beam_x, new_y);
-
-            match self.grid.get(below) {
-                Some(Cell::Empty) => {
-                    beams.insert((beam_x, new_y));
-                }
-                Some(Cell::Splitter) => {
-                    let mut did_split = false;
-
-                    // left
-                    if let Some(left_x) = beam_x.checked_sub(1) {
-                        if matches!(self.grid.get(self.index(left_x, new_y)), Some(Cell::Empty)) {
-                            split_beams.insert((left_x, new_y));
-                            did_split = true;
-                        }
-                    }
-
-                    // right
-                    if let Some(right_x) = beam_x.checked_add(1) {
-                        if matches!(self.grid.get(self.index(right_x, new_y)), Some(Cell::Empty)) {
-                            split_beams.insert((right_x, new_y));
-                            did_split = true;
-                        }
-                    }
-
-                    if did_split {
-                        num_splits += 1;
-                    }
-                }
-                _ => {}
-            }
-        }
-
-        BeamResult {
-            beams,
-            split_beams,
-            num_splits,
-        }
+    fn finish(&mut self) -> Stats {
+        Driver::new().run(self)
     }
 
-    fn step(&mut self) -> bool {
-        let result = self.advance_beams();
-
-        if !result.split_beams.is_empty() {
-            self.num_splits += result.num_splits;
-        }
-
-        let mut new_beams = result.beams;
-        new_beams.extend(result.split_beams);
-
-        for &(x, y) in &new_beams {
-            let idx = self.index(x, y);
-            assert!(matches!(self.grid[idx], Cell::Empty));
-            self.grid[idx] = Cell::Beam;
-        }
-
-        let has_new = !new_beams.is_empty();
-        self.beams = new_beams;
-        has_new
+    fn render(&self, mode: ColorMode) -> String {
+        render(self, mode, |_, _, cell| (cell.to_char(), cell.style()))
     }
 
-    fn finish(&mut self) {
-        while self.step() {}
+    fn image(&self, scale: usize) -> Image {
+        Image::from_grid(self, scale, |_, _, cell| cell.color())
     }
 
-    fn finish_timelines(self) -> usize {
-        let start = self.beams.iter().next().unwrap();
-        let mut memo = HashMap::new();
-        self.dfs(*start, &mut memo)
+    fn finish_timelines(self) -> Answer {
+        count_paths(&self, self.start, |&(_, y)| y + 1 >= self.height())
     }
+}
 
-    fn dfs(&self, (x, y): (usize, usize), memo: &mut HashMap<(usize, usize), usize>) -> usize {
-        if y + 1 >= self.height() {
-            return 1;
-        }
+// a beam at the given position can move to these positions in the next row
+impl Graph for Machine {
+    type Node = (usize, usize);
 
-        if let Some(&cached) = memo.get(&(x, y)) {
-            return cached;
+    fn successors(&self, &(x, y): &Self::Node) -> Vec<Self::Node> {
+        if y + 1 >= self.height() {
+            return vec![];
         }
 
         let below = self.index(x, y + 1);
-        let result = match self.grid[below] {
-            Cell::Empty => self.dfs((x, y + 1), memo),
+        match self.grid[below] {
+            Cell::Empty => vec![(x, y + 1)],
             Cell::Splitter => {
-                let mut sum = 0;
+                let mut successors = vec![];
 
                 if x > 0 {
                     let left = self.index(x - 1, y + 1);
                     if matches!(self.grid[left], Cell::Empty) {
-                        sum += self.dfs((x - 1, y + 1), memo);
+                        successors.push((x - 1, y + 1));
                     }
                 }
 
                 let right = self.index(x + 1, y + 1);
                 if matches!(self.grid[right], Cell::Empty) {
-                    sum += self.dfs((x + 1, y + 1), memo);
+                    successors.push((x + 1, y + 1));
                 }
-                sum
+
+                successors
             }
-            _ => 0,
-        };
+            _ => vec![],
+        }
+    }
+}
+
+impl Grid2D for Machine {
+    type Cell = Cell;
+
+    fn width(&self) -> usize {
+        self.width
+    }
 
-        memo.insert((x, y), result);
+    fn height(&self) -> usize {
+        self.height()
+    }
 
-        result
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        if x >= self.width {
+            return None;
+        }
+
+        self.grid.get(self.index(x, y)).cloned()
     }
+}
 
-    fn to_string(&self) -> String {
-        let mut str = String::with_capacity(self.grid.len());
+// every step moves all beams one row down
+impl Simulation for Machine {
+    fn step(&mut self) -> bool {
+        let grid = FlatGrid::new(&self.grid, self.width);
+        let num_splits = &mut self.num_splits;
+
+        let has_beams = self.beams.step(&grid, |ray, cell| match cell {
+            Cell::Empty => Hit::Pass,
+            Cell::Splitter => {
+                let (x, y) = ray.pos;
+
+                // the beam continues on both sides of the splitter, as long as there is room
+                let rays = [x.checked_sub(1), x.checked_add(1)]
+                    .into_iter()
+                    .flatten()
+                    .filter(|&x| matches!(grid.cell(x, y), Some(Cell::Empty)))
+                    .map(|x| Ray::new((x, y), Dir::Down))
+                    .collect::<Vec<_>>();
+
+                if !rays.is_empty() {
+                    *num_splits += 1;
+                }
 
-        for (index, cell) in self.grid.iter().enumerate() {
-            if index > 0 && index % self.width == 0 {
-                str.push('\n');
+                Hit::Split(rays)
             }
+            Cell::Start | Cell::Beam => Hit::Absorb,
+        });
 
-            str.push(cell.to_char());
+        for ray in self.beams.rays() {
+            let idx = self.index(ray.pos.0, ray.pos.1);
+            self.grid[idx] = Cell::Beam;
         }
 
-        str
+        has_beams
+    }
+}
+
+impl Stepper for Machine {
+    fn view(&self, mode: ColorMode) -> String {
+        self.render(mode)
+    }
+
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![
+            ("splits", self.num_splits.to_string()),
+            ("beams", self.beams.rays().len().to_string()),
+        ]
+    }
+}
+
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(ColorMode::Never))
     }
 }
 
@@ -240,9 +287,10 @@ fn xy_from_index(width: usize, index: usize) -> (usize, usize) {
 #[cfg(test)]
 mod test {
     use crate::Machine;
+    use aoc::sim::Simulation;
     use pretty_assertions::assert_eq;
 
-    const DEMO_INPUT: &'static str = r#"
+    const DEMO_INPUT: &str = r#"
 .......S.......
 ...............
 .......^.......
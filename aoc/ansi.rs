@@ -0,0 +1,183 @@
+use crate::grid::Grid2D;
+use std::io::IsTerminal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Color {
+    fn code(&self) -> u8 {
+        match self {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::White => 7,
+        }
+    }
+}
+
+/// How a single cell is printed, the default prints it unchanged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Style {
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+    pub bold: bool,
+    pub dim: bool,
+}
+
+impl Style {
+    pub const PLAIN: Style = Style {
+        fg: None,
+        bg: None,
+        bold: false,
+        dim: false,
+    };
+
+    pub fn fg(color: Color) -> Self {
+        Style {
+            fg: Some(color),
+            ..Style::PLAIN
+        }
+    }
+
+    pub fn on(self, color: Color) -> Self {
+        Style {
+            bg: Some(color),
+            ..self
+        }
+    }
+
+    pub fn bold(self) -> Self {
+        Style { bold: true, ..self }
+    }
+
+    pub fn dim(self) -> Self {
+        Style { dim: true, ..self }
+    }
+
+    fn escape(&self) -> String {
+        let mut codes = vec![];
+
+        if self.bold {
+            codes.push("1".to_string());
+        }
+
+        if self.dim {
+            codes.push("2".to_string());
+        }
+
+        if let Some(fg) = self.fg {
+            codes.push(format!("3{}", fg.code()));
+        }
+
+        if let Some(bg) = self.bg {
+            codes.push(format!("4{}", bg.code()));
+        }
+
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// colors only if stdout is a terminal and `NO_COLOR` isn't set
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorMode::Auto => {
+                std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+            }
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+/// Renders one line per grid row, `style` maps every cell to its character and style
+pub fn render<G, F>(grid: &G, mode: ColorMode, mut style: F) -> String
+where
+    G: Grid2D,
+    F: FnMut(usize, usize, G::Cell) -> (char, Style),
+{
+    let color = mode.enabled();
+    let mut str = String::with_capacity((grid.width() + 1) * grid.height());
+
+    for y in 0..grid.height() {
+        if y > 0 {
+            str.push('\n');
+        }
+
+        let mut current = Style::PLAIN;
+
+        for x in 0..grid.width() {
+            let Some(cell) = grid.cell(x, y) else {
+                continue;
+            };
+
+            let (c, cell_style) = style(x, y, cell);
+
+            // escape codes are only written when the style changes
+            if color && cell_style != current {
+                if current != Style::PLAIN {
+                    str.push_str(RESET);
+                }
+
+                if cell_style != Style::PLAIN {
+                    str.push_str(&cell_style.escape());
+                }
+
+                current = cell_style;
+            }
+
+            str.push(c);
+        }
+
+        if current != Style::PLAIN {
+            str.push_str(RESET);
+        }
+    }
+
+    str
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ansi::{render, Color, ColorMode, Style};
+    use crate::grid::FlatGrid;
+
+    #[test]
+    fn test_render() {
+        let cells = vec![1, 1, 0, 0, 1, 2];
+        let grid = FlatGrid::new(&cells, 3);
+        let style = |_, _, cell| match cell {
+            0 => ('.', Style::PLAIN),
+            1 => ('#', Style::fg(Color::Green)),
+            _ => ('*', Style::fg(Color::Red).on(Color::Black).bold()),
+        };
+
+        assert_eq!("##.\n.#*", render(&grid, ColorMode::Never, style));
+        assert_eq!(
+            "\x1b[32m##\x1b[0m.\n.\x1b[32m#\x1b[0m\x1b[1;31;40m*\x1b[0m",
+            render(&grid, ColorMode::Always, style)
+        );
+    }
+}
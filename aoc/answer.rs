@@ -0,0 +1,268 @@
+use crate::bigint::BigUint;
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Mul, MulAssign};
+
+/// Non-negative puzzle answer that can't silently overflow
+///
+/// Arithmetic is checked and promotes to a `BigUint` once a value doesn't fit into an `u64`
+/// anymore. With the `strict` feature enabled an overflow panics instead, pointing at the
+/// operation that caused it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Answer(Repr);
+
+// Big is only used for values above u64::MAX so the derived Eq and Hash stay correct
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Repr {
+    Small(u64),
+    Big(BigUint),
+}
+
+impl Answer {
+    pub const ZERO: Answer = Answer(Repr::Small(0));
+    pub const ONE: Answer = Answer(Repr::Small(1));
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match &self.0 {
+            Repr::Small(v) => Some(*v),
+            Repr::Big(_) => None,
+        }
+    }
+
+    pub fn is_big(&self) -> bool {
+        matches!(self.0, Repr::Big(_))
+    }
+
+    fn to_big(&self) -> BigUint {
+        match &self.0 {
+            Repr::Small(v) => BigUint::from(*v),
+            Repr::Big(v) => v.clone(),
+        }
+    }
+
+    fn from_big(value: BigUint) -> Self {
+        match value.to_u64() {
+            Some(v) => Answer(Repr::Small(v)),
+            None => Answer(Repr::Big(value)),
+        }
+    }
+
+    #[track_caller]
+    fn overflow(lhs: &Answer, rhs: &Answer, op: char) {
+        if cfg!(feature = "strict") {
+            panic!("arithmetic overflow: {lhs} {op} {rhs} doesn't fit into u64");
+        }
+    }
+}
+
+impl Add for Answer {
+    type Output = Answer;
+
+    #[track_caller]
+    fn add(self, rhs: Answer) -> Answer {
+        if let (Repr::Small(a), Repr::Small(b)) = (&self.0, &rhs.0)
+            && let Some(sum) = a.checked_add(*b)
+        {
+            return Answer(Repr::Small(sum));
+        }
+
+        Answer::overflow(&self, &rhs, '+');
+        Answer::from_big(&self.to_big() + &rhs.to_big())
+    }
+}
+
+impl Mul for Answer {
+    type Output = Answer;
+
+    #[track_caller]
+    fn mul(self, rhs: Answer) -> Answer {
+        if let (Repr::Small(a), Repr::Small(b)) = (&self.0, &rhs.0)
+            && let Some(product) = a.checked_mul(*b)
+        {
+            return Answer(Repr::Small(product));
+        }
+
+        Answer::overflow(&self, &rhs, '*');
+        Answer::from_big(&self.to_big() * &rhs.to_big())
+    }
+}
+
+impl Add<u64> for Answer {
+    type Output = Answer;
+
+    #[track_caller]
+    fn add(self, rhs: u64) -> Answer {
+        self + Answer::from(rhs)
+    }
+}
+
+impl Mul<u64> for Answer {
+    type Output = Answer;
+
+    #[track_caller]
+    fn mul(self, rhs: u64) -> Answer {
+        self * Answer::from(rhs)
+    }
+}
+
+impl<T> AddAssign<T> for Answer
+where
+    Answer: Add<T, Output = Answer>,
+{
+    #[track_caller]
+    fn add_assign(&mut self, rhs: T) {
+        *self = std::mem::replace(self, Answer::ZERO) + rhs;
+    }
+}
+
+impl<T> MulAssign<T> for Answer
+where
+    Answer: Mul<T, Output = Answer>,
+{
+    #[track_caller]
+    fn mul_assign(&mut self, rhs: T) {
+        *self = std::mem::replace(self, Answer::ZERO) * rhs;
+    }
+}
+
+impl Sum for Answer {
+    fn sum<I: Iterator<Item = Answer>>(iter: I) -> Answer {
+        iter.fold(Answer::ZERO, |acc, v| acc + v)
+    }
+}
+
+impl<'a> Sum<&'a Answer> for Answer {
+    fn sum<I: Iterator<Item = &'a Answer>>(iter: I) -> Answer {
+        iter.fold(Answer::ZERO, |acc, v| acc + v.clone())
+    }
+}
+
+impl Sum<u64> for Answer {
+    fn sum<I: Iterator<Item = u64>>(iter: I) -> Answer {
+        iter.fold(Answer::ZERO, |acc, v| acc + v)
+    }
+}
+
+impl Product for Answer {
+    fn product<I: Iterator<Item = Answer>>(iter: I) -> Answer {
+        iter.fold(Answer::ONE, |acc, v| acc * v)
+    }
+}
+
+impl Product<u64> for Answer {
+    fn product<I: Iterator<Item = u64>>(iter: I) -> Answer {
+        iter.fold(Answer::ONE, |acc, v| acc * v)
+    }
+}
+
+macro_rules! impl_from {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer(Repr::Small(value as u64))
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(u8, u16, u32, u64, usize);
+
+impl PartialEq<u64> for Answer {
+    fn eq(&self, other: &u64) -> bool {
+        self.to_u64() == Some(*other)
+    }
+}
+
+impl PartialEq<Answer> for u64 {
+    fn eq(&self, other: &Answer) -> bool {
+        other == self
+    }
+}
+
+impl Ord for Answer {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (&self.0, &other.0) {
+            (Repr::Small(a), Repr::Small(b)) => a.cmp(b),
+            (Repr::Small(_), Repr::Big(_)) => Ordering::Less,
+            (Repr::Big(_), Repr::Small(_)) => Ordering::Greater,
+            (Repr::Big(a), Repr::Big(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Answer {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Repr::Small(v) => fmt::Display::fmt(v, f),
+            Repr::Big(v) => fmt::Display::fmt(v, f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::answer::Answer;
+
+    #[test]
+    fn test_small() {
+        let a = Answer::from(40u64) + 2;
+        assert_eq!(Answer::from(42u64), a);
+        assert_eq!(Some(84), (a * 2).to_u64());
+        assert_eq!(
+            Answer::from(10u64),
+            [1u64, 2, 3, 4].into_iter().sum::<Answer>()
+        );
+        assert_eq!(
+            Answer::from(24u64),
+            [1u64, 2, 3, 4].into_iter().product::<Answer>()
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn test_promotes_on_overflow() {
+        let max = Answer::from(u64::MAX);
+
+        let sum = max.clone() + 1;
+        assert!(sum.is_big());
+        assert_eq!("18446744073709551616", sum.to_string());
+        assert!(sum > max);
+
+        let product = [u64::MAX, u64::MAX].into_iter().product::<Answer>();
+        assert_eq!(
+            "340282366920938463426481119284349108225",
+            product.to_string()
+        );
+
+        // 12 digits more than fit into u64
+        let digits = "1234567890123456789012345678901";
+        let n = digits
+            .bytes()
+            .fold(Answer::ZERO, |acc, b| acc * 10 + u64::from(b - b'0'));
+        assert_eq!(digits, n.to_string());
+    }
+
+    #[test]
+    #[cfg(feature = "strict")]
+    #[should_panic(expected = "arithmetic overflow: 18446744073709551615 + 1")]
+    fn test_strict_panics_on_overflow() {
+        let _ = Answer::from(u64::MAX) + 1;
+    }
+
+    #[test]
+    fn test_assign() {
+        let mut a = Answer::ONE;
+        a += 4;
+        a *= Answer::from(3u8);
+        assert_eq!(Answer::from(15u64), a);
+    }
+}
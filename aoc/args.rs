@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+/// True if `--name` was passed on the command line
+pub fn flag(name: &str) -> bool {
+    has_flag(std::env::args().skip(1), name)
+}
+
+/// Value of `--name value` or `--name=value` from the command line
+pub fn value(name: &str) -> Option<String> {
+    find_value(std::env::args().skip(1), name)
+}
+
+/// Every value passed for a repeatable `--name`, in order
+pub fn values(name: &str) -> Vec<String> {
+    find_values(std::env::args().skip(1), name)
+}
+
+/// Parsed value of `--name`, `default` if it wasn't passed
+pub fn parsed<T: FromStr>(name: &str, default: T) -> T {
+    match value(name) {
+        Some(value) => value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid value {value} for {name}")),
+        None => default,
+    }
+}
+
+fn has_flag<I: Iterator<Item = String>>(mut args: I, name: &str) -> bool {
+    args.any(|arg| arg == name)
+}
+
+fn find_value<I: Iterator<Item = String>>(args: I, name: &str) -> Option<String> {
+    find_values(args, name).into_iter().next()
+}
+
+fn find_values<I: Iterator<Item = String>>(mut args: I, name: &str) -> Vec<String> {
+    let mut values = vec![];
+
+    while let Some(arg) = args.next() {
+        if arg == name {
+            values.extend(args.next());
+            continue;
+        }
+
+        if let Some(value) = arg
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            values.push(value.to_string());
+        }
+    }
+
+    values
+}
+
+#[cfg(test)]
+mod test {
+    use crate::args::{find_value, find_values, has_flag};
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn test_args() {
+        assert!(has_flag(args("--render --scale 4"), "--render"));
+        assert!(!has_flag(args("--rendering"), "--render"));
+
+        assert_eq!(
+            Some("4".to_string()),
+            find_value(args("--render --scale 4"), "--scale")
+        );
+        assert_eq!(
+            Some("out.png".to_string()),
+            find_value(args("--export=out.png"), "--export")
+        );
+        assert_eq!(None, find_value(args("--scale"), "--scale"));
+        assert_eq!(None, find_value(args("--scales=4"), "--scale"));
+
+        assert_eq!(
+            vec!["red=12", "blue=3"],
+            find_values(args("--limit red=12 --fps 2 --limit=blue=3"), "--limit")
+        );
+        assert!(find_values(args("--fps 2"), "--limit").is_empty());
+    }
+}
@@ -0,0 +1,185 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul};
+
+/// Arbitrary size unsigned integer, just enough to add and multiply puzzle answers
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    // little endian, never has trailing zero limbs
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        BigUint { limbs: vec![] }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.as_slice() {
+            [] => Some(0),
+            [low] => Some(u64::from(*low)),
+            [low, high] => Some(u64::from(*low) | (u64::from(*high) << 32)),
+            _ => None,
+        }
+    }
+
+    fn normalize(mut self) -> Self {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+
+        self
+    }
+
+    // divides in place by a small divisor and returns the remainder
+    fn div_rem_small(&mut self, divisor: u32) -> u32 {
+        let mut rem = 0u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (rem << 32) | u64::from(*limb);
+            *limb = (current / u64::from(divisor)) as u32;
+            rem = current % u64::from(divisor);
+        }
+
+        *self = std::mem::take(self).normalize();
+        rem as u32
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let len = self.limbs.len().max(other.limbs.len());
+        let mut limbs = Vec::with_capacity(len + 1);
+        let mut carry = 0u64;
+
+        for i in 0..len {
+            let sum = u64::from(self.limbs.get(i).copied().unwrap_or(0))
+                + u64::from(other.limbs.get(i).copied().unwrap_or(0))
+                + carry;
+
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+
+        limbs.push(carry as u32);
+        BigUint { limbs }.normalize()
+    }
+}
+
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let current = u64::from(a) * u64::from(b) + u64::from(limbs[i + j]) + carry;
+                limbs[i + j] = current as u32;
+                carry = current >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint { limbs }.normalize()
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        BigUint {
+            limbs: vec![value as u32, (value >> 32) as u32],
+        }
+        .normalize()
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const CHUNK: u32 = 1_000_000_000;
+
+        if self.is_zero() {
+            return f.pad("0");
+        }
+
+        // 9 decimal digits at a time, least significant first
+        let mut n = self.clone();
+        let mut chunks = vec![];
+
+        while !n.is_zero() {
+            chunks.push(n.div_rem_small(CHUNK));
+        }
+
+        let mut str = chunks.last().unwrap().to_string();
+
+        for chunk in chunks.iter().rev().skip(1) {
+            str.push_str(&format!("{chunk:09}"));
+        }
+
+        f.pad(&str)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bigint::BigUint;
+
+    #[test]
+    fn test_add() {
+        let a = BigUint::from(u64::MAX);
+        assert_eq!("18446744073709551616", (&a + &BigUint::from(1)).to_string());
+        assert_eq!(None, (&a + &BigUint::from(1)).to_u64());
+        assert_eq!(Some(7), (&BigUint::from(3) + &BigUint::from(4)).to_u64());
+    }
+
+    #[test]
+    fn test_mul() {
+        let a = BigUint::from(u64::MAX);
+        assert_eq!(
+            "340282366920938463426481119284349108225",
+            (&a * &a).to_string()
+        );
+        assert_eq!(BigUint::zero(), &a * &BigUint::zero());
+
+        // 30!
+        let fac = (1..=30).fold(BigUint::from(1), |acc, n| &acc * &BigUint::from(n));
+        assert_eq!("265252859812191058636308480000000", fac.to_string());
+    }
+
+    #[test]
+    fn test_cmp() {
+        let big = &BigUint::from(u64::MAX) + &BigUint::from(1);
+        assert!(big > BigUint::from(u64::MAX));
+        assert!(BigUint::from(2) < BigUint::from(3));
+        assert_eq!("0", BigUint::zero().to_string());
+        assert_eq!("1000000000", BigUint::from(1_000_000_000).to_string());
+    }
+}
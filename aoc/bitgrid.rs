@@ -0,0 +1,389 @@
+use crate::grid::{neighbors, Connectivity, Grid2D};
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// Boolean grid with every row packed into `u64` words, bit `i` of word `w` is x = 64 * w + i
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BitGrid {
+    // bits past the width are always zero so Eq, Hash and count_ones don't see them
+    words: Vec<u64>,
+    width: usize,
+    height: usize,
+    row_words: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        let row_words = width.div_ceil(WORD_BITS);
+
+        BitGrid {
+            words: vec![0; row_words * height],
+            width,
+            height,
+            row_words,
+        }
+    }
+
+    pub fn from_fn<F>(width: usize, height: usize, mut f: F) -> Self
+    where
+        F: FnMut(usize, usize) -> bool,
+    {
+        let mut grid = BitGrid::new(width, height);
+
+        for y in 0..height {
+            for x in 0..width {
+                grid.set(x, y, f(x, y));
+            }
+        }
+
+        grid
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        assert!(
+            x < self.width && y < self.height,
+            "{x}x{y} is out of bounds"
+        );
+        let (word, bit) = self.position(x, y);
+
+        self.words[word] >> bit & 1 == 1
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, value: bool) {
+        assert!(
+            x < self.width && y < self.height,
+            "{x}x{y} is out of bounds"
+        );
+        let (word, bit) = self.position(x, y);
+
+        match value {
+            true => self.words[word] |= 1 << bit,
+            false => self.words[word] &= !(1 << bit),
+        }
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    /// Positions of all set cells in row order
+    pub fn iter_ones(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.words
+            .iter()
+            .enumerate()
+            .flat_map(move |(index, &word)| {
+                let y = index / self.row_words;
+                let x0 = (index % self.row_words) * WORD_BITS;
+
+                let mut rest = word;
+                std::iter::from_fn(move || {
+                    if rest == 0 {
+                        return None;
+                    }
+
+                    let bit = rest.trailing_zeros() as usize;
+                    rest &= rest - 1;
+                    Some((x0 + bit, y))
+                })
+            })
+    }
+
+    /// Number of set neighbors of a single cell
+    pub fn neighbor_count(&self, x: usize, y: usize, connectivity: Connectivity) -> usize {
+        neighbors((x, y), self.width, self.height, connectivity)
+            .filter(|&(nx, ny)| self.get(nx, ny))
+            .count()
+    }
+
+    /// Number of set neighbors for every cell at once. The counts are kept as bit planes, so each
+    /// word handles 64 cells with a handful of shifts and boolean ops.
+    pub fn neighbor_counts(&self, connectivity: Connectivity) -> NeighborCounts {
+        let mut planes: [Vec<u64>; 4] = std::array::from_fn(|_| vec![0; self.words.len()]);
+        // rows shifted by dx = -1, 0 and 1
+        let mut shifted = vec![vec![0u64; self.row_words]; 3];
+
+        for y in 0..self.height {
+            let base = y * self.row_words;
+            let mut sum: [Vec<u64>; 4] = std::array::from_fn(|_| vec![0; self.row_words]);
+
+            for dy in -1..=1 {
+                let Some(ny) = y.checked_add_signed(dy).filter(|ny| *ny < self.height) else {
+                    continue;
+                };
+
+                for (dx, row) in (-1..=1).zip(shifted.iter_mut()) {
+                    self.shift_row(ny, dx, row);
+                }
+
+                for &(dx, _) in connectivity.offsets().iter().filter(|(_, ody)| *ody == dy) {
+                    let row = &shifted[(dx + 1) as usize];
+
+                    // ripple carry add of a single bit into every 64 counters of a word
+                    for (w, &bits) in row.iter().enumerate() {
+                        let mut carry = bits;
+
+                        for plane in sum.iter_mut() {
+                            let next = plane[w] & carry;
+                            plane[w] ^= carry;
+                            carry = next;
+                        }
+                    }
+                }
+            }
+
+            for (plane, row) in planes.iter_mut().zip(sum) {
+                plane[base..base + self.row_words].copy_from_slice(&row);
+            }
+        }
+
+        NeighborCounts {
+            planes,
+            shape: BitGrid::new(self.width, self.height),
+        }
+    }
+
+    fn position(&self, x: usize, y: usize) -> (usize, usize) {
+        (y * self.row_words + x / WORD_BITS, x % WORD_BITS)
+    }
+
+    // row `y` shifted so that bit x holds the cell at x + dx
+    fn shift_row(&self, y: usize, dx: isize, out: &mut [u64]) {
+        let row = &self.words[y * self.row_words..(y + 1) * self.row_words];
+
+        for (w, word) in out.iter_mut().enumerate() {
+            *word = match dx {
+                0 => row[w],
+                1 => row[w] >> 1 | row.get(w + 1).map_or(0, |next| next << (WORD_BITS - 1)),
+                -1 => row[w] << 1 | w.checked_sub(1).map_or(0, |p| row[p] >> (WORD_BITS - 1)),
+                _ => panic!("only direct neighbors are supported, got dx = {dx}"),
+            };
+        }
+
+        if let Some(last) = out.last_mut() {
+            *last &= self.row_mask(self.row_words - 1);
+        }
+    }
+
+    // valid bits of word `w` within a row
+    fn row_mask(&self, w: usize) -> u64 {
+        let valid = self.width - w * WORD_BITS;
+
+        match valid >= WORD_BITS {
+            true => u64::MAX,
+            false => (1 << valid) - 1,
+        }
+    }
+
+    fn clear_padding(&mut self) {
+        for y in 0..self.height {
+            for w in 0..self.row_words {
+                self.words[y * self.row_words + w] &= self.row_mask(w);
+            }
+        }
+    }
+
+    fn zip_with<F: Fn(u64, u64) -> u64>(&mut self, other: &BitGrid, f: F) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "grid sizes don't match"
+        );
+
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a = f(*a, *b);
+        }
+    }
+}
+
+impl Grid2D for BitGrid {
+    type Cell = bool;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        (x < self.width && y < self.height).then(|| self.get(x, y))
+    }
+}
+
+/// Per cell neighbor counts as produced by `BitGrid::neighbor_counts`
+#[derive(Debug, Clone)]
+pub struct NeighborCounts {
+    // bit i of every count, counts never exceed 8
+    planes: [Vec<u64>; 4],
+    shape: BitGrid,
+}
+
+impl NeighborCounts {
+    pub fn get(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.shape.width && y < self.shape.height,
+            "{x}x{y} is out of bounds"
+        );
+        let (word, bit) = self.shape.position(x, y);
+
+        self.planes
+            .iter()
+            .enumerate()
+            .map(|(i, plane)| ((plane[word] >> bit & 1) as usize) << i)
+            .sum()
+    }
+
+    /// Cells with fewer than `n` set neighbors
+    pub fn less_than(&self, n: usize) -> BitGrid {
+        let mut res = self.shape.clone();
+
+        for (index, word) in res.words.iter_mut().enumerate() {
+            // bitwise compare against every count below n
+            *word = (0..n.min(16)).fold(0, |acc, count| {
+                acc | self
+                    .planes
+                    .iter()
+                    .enumerate()
+                    .fold(u64::MAX, |eq, (i, plane)| match count >> i & 1 {
+                        1 => eq & plane[index],
+                        _ => eq & !plane[index],
+                    })
+            });
+        }
+
+        res.clear_padding();
+        res
+    }
+
+    /// Cells with at least `n` set neighbors
+    pub fn at_least(&self, n: usize) -> BitGrid {
+        !&self.less_than(n)
+    }
+}
+
+macro_rules! impl_bit_op {
+    ($op:ident, $fn:ident, $op_assign:ident, $fn_assign:ident, $sym:tt) => {
+        impl $op for &BitGrid {
+            type Output = BitGrid;
+
+            fn $fn(self, rhs: &BitGrid) -> BitGrid {
+                let mut res = self.clone();
+                res.zip_with(rhs, |a, b| a $sym b);
+                res
+            }
+        }
+
+        impl $op_assign<&BitGrid> for BitGrid {
+            fn $fn_assign(&mut self, rhs: &BitGrid) {
+                self.zip_with(rhs, |a, b| a $sym b);
+            }
+        }
+    };
+}
+
+impl_bit_op!(BitAnd, bitand, BitAndAssign, bitand_assign, &);
+impl_bit_op!(BitOr, bitor, BitOrAssign, bitor_assign, |);
+impl_bit_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, ^);
+
+impl Not for &BitGrid {
+    type Output = BitGrid;
+
+    fn not(self) -> BitGrid {
+        let mut res = self.clone();
+
+        for word in res.words.iter_mut() {
+            *word = !*word;
+        }
+
+        res.clear_padding();
+        res
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::bitgrid::BitGrid;
+    use crate::grid::Connectivity;
+
+    // crosses a word boundary and has padding in the last word
+    fn pattern() -> BitGrid {
+        BitGrid::from_fn(70, 5, |x, y| (x * 7 + y * 3) % 5 < 2 || x == 63 || x == 64)
+    }
+
+    #[test]
+    fn test_get_set() {
+        let mut g = BitGrid::new(70, 2);
+        assert!(g.is_empty());
+
+        g.set(0, 0, true);
+        g.set(64, 1, true);
+        g.set(69, 1, true);
+        assert!(g.get(64, 1));
+        assert!(!g.get(63, 1));
+        assert_eq!(3, g.count_ones());
+        assert_eq!(
+            vec![(0, 0), (64, 1), (69, 1)],
+            g.iter_ones().collect::<Vec<_>>()
+        );
+
+        g.set(64, 1, false);
+        assert_eq!(2, g.count_ones());
+    }
+
+    #[test]
+    fn test_ops() {
+        let a = pattern();
+        let b = BitGrid::from_fn(70, 5, |x, _| x % 2 == 0);
+
+        let not = !&a;
+        assert_eq!(70 * 5, a.count_ones() + not.count_ones());
+        assert!((&a & &not).is_empty());
+
+        for (x, y) in (0..70).flat_map(|x| (0..5).map(move |y| (x, y))) {
+            assert_eq!(a.get(x, y) && b.get(x, y), (&a & &b).get(x, y));
+            assert_eq!(a.get(x, y) || b.get(x, y), (&a | &b).get(x, y));
+            assert_eq!(a.get(x, y) != b.get(x, y), (&a ^ &b).get(x, y));
+        }
+
+        let mut c = a.clone();
+        c ^= &a;
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn test_neighbor_counts() {
+        let g = pattern();
+
+        for connectivity in [Connectivity::Four, Connectivity::Eight] {
+            let counts = g.neighbor_counts(connectivity);
+            let few = counts.less_than(3);
+
+            for y in 0..g.height() {
+                for x in 0..g.width() {
+                    let expected = g.neighbor_count(x, y, connectivity);
+                    assert_eq!(expected, counts.get(x, y), "{x}x{y}");
+                    assert_eq!(expected < 3, few.get(x, y), "{x}x{y}");
+                }
+            }
+
+            assert_eq!(70 * 5, few.count_ones() + counts.at_least(3).count_ones());
+        }
+
+        let full = !&BitGrid::new(3, 3);
+        assert_eq!(8, full.neighbor_counts(Connectivity::Eight).get(1, 1));
+    }
+}
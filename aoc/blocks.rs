@@ -0,0 +1,145 @@
+use std::fmt;
+
+/// Iterator over blank-line-separated blocks of an input.
+///
+/// Runs of blank (or whitespace only) lines count as a single separator, leading and trailing
+/// blank lines are ignored and both `\n` and `\r\n` line endings are supported. Every block is
+/// a slice of the original input without its final line ending.
+pub struct Blocks<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Blocks<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Blocks { rest: input }
+    }
+}
+
+impl<'a> Iterator for Blocks<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // skip all blank lines in front of the next block
+        loop {
+            if self.rest.is_empty() {
+                return None;
+            }
+
+            let (line, rest) = split_line(self.rest);
+
+            if !line.trim().is_empty() {
+                break;
+            }
+
+            self.rest = rest;
+        }
+
+        let input = self.rest;
+        let mut end = 0;
+
+        loop {
+            let (line, rest) = split_line(self.rest);
+
+            if line.trim().is_empty() {
+                break;
+            }
+
+            end = input.len() - self.rest.len() + line.len();
+            self.rest = rest;
+
+            if self.rest.is_empty() {
+                break;
+            }
+        }
+
+        Some(&input[..end])
+    }
+}
+
+/// Splits off the first line, returns it without its line ending together with the remainder
+fn split_line(input: &str) -> (&str, &str) {
+    let (line, rest) = input.split_once('\n').unwrap_or((input, ""));
+    (line.strip_suffix('\r').unwrap_or(line), rest)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum BlocksError {
+    Count { expected: usize, found: usize },
+}
+
+impl fmt::Display for BlocksError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlocksError::Count { expected, found } => {
+                write!(f, "expected {expected} blocks but found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for BlocksError {}
+
+/// Splits the input into exactly `N` blank-line-separated blocks
+///
+/// ```
+/// let [ranges, ids] = aoc::blocks::blocks::<2>("1-2\n3-4\n\n5\n").unwrap();
+/// assert_eq!("1-2\n3-4", ranges);
+/// assert_eq!("5", ids);
+/// ```
+pub fn blocks<const N: usize>(input: &str) -> Result<[&str; N], BlocksError> {
+    let blocks = Blocks::new(input).collect::<Vec<&str>>();
+    let found = blocks.len();
+
+    blocks
+        .try_into()
+        .map_err(|_| BlocksError::Count { expected: N, found })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::blocks::{blocks, Blocks, BlocksError};
+
+    #[test]
+    fn test_blocks() {
+        let input = "a\nb\n\nc\n\nd\ne\nf";
+        assert_eq!(
+            vec!["a\nb", "c", "d\ne\nf"],
+            Blocks::new(input).collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn test_blocks_blank_runs() {
+        let input = "\n\na\n\n  \n\nb\n\n\n";
+        assert_eq!(vec!["a", "b"], Blocks::new(input).collect::<Vec<&str>>());
+    }
+
+    #[test]
+    fn test_blocks_crlf() {
+        let input = "a\r\nb\r\n\r\nc\r\n";
+        assert_eq!(
+            vec!["a\r\nb", "c"],
+            Blocks::new(input).collect::<Vec<&str>>()
+        );
+    }
+
+    #[test]
+    fn test_blocks_empty() {
+        assert_eq!(None, Blocks::new("").next());
+        assert_eq!(None, Blocks::new("\n \n").next());
+    }
+
+    #[test]
+    fn test_blocks_destructuring() {
+        let [first, second, third] = blocks::<3>("1\n\n2\n\n3").unwrap();
+        assert_eq!(("1", "2", "3"), (first, second, third));
+
+        assert_eq!(
+            Err(BlocksError::Count {
+                expected: 2,
+                found: 3
+            }),
+            blocks::<2>("1\n\n2\n\n3")
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// States `start`, `start + 1`, ... repeat every `length` steps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    pub start: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps step `n` to the earliest step with the same state
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            return n;
+        }
+
+        self.start + (n - self.start) % self.length
+    }
+}
+
+/// Applies `step` to `initial` until a state repeats. Only terminates if the simulation has
+/// finitely many states, a simulation that stops changing is a cycle of length 1.
+pub fn find_cycle<S, F>(initial: S, step: F) -> Cycle
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    find_cycle_by_key(initial, step, |state| state.clone())
+}
+
+/// Same as `find_cycle` but only compares `key(state)`, which has to capture everything that
+/// influences the following states
+pub fn find_cycle_by_key<S, K, F, G>(initial: S, step: F, key: G) -> Cycle
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let (_, cycle) = run(initial, step, key, usize::MAX);
+    cycle.expect("should always find a cycle without a step limit")
+}
+
+/// State after `n` steps, fast-forwards as soon as a cycle shows up so `n` can be huge
+pub fn nth_state<S, F>(initial: S, step: F, n: usize) -> S
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    nth_state_by_key(initial, step, |state| state.clone(), n)
+}
+
+/// Same as `nth_state` but only compares `key(state)`, see `find_cycle_by_key`. After a
+/// fast-forward the result is the earlier state with the same key, so fields outside `key` come
+/// from that state and not from step `n`.
+pub fn nth_state_by_key<S, K, F, G>(initial: S, step: F, key: G, n: usize) -> S
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let (mut history, cycle) = run(initial, step, key, n);
+
+    let index = match cycle {
+        Some(cycle) => cycle.reduce(n),
+        None => n,
+    };
+
+    history.swap_remove(index)
+}
+
+// steps until a state repeats or `limit` steps are done, returns every state seen
+fn run<S, K, F, G>(initial: S, mut step: F, mut key: G, limit: usize) -> (Vec<S>, Option<Cycle>)
+where
+    K: Hash + Eq,
+    F: FnMut(&S) -> S,
+    G: FnMut(&S) -> K,
+{
+    let mut seen = HashMap::from([(key(&initial), 0)]);
+    let mut history = vec![initial];
+
+    while history.len() <= limit {
+        let next = step(history.last().unwrap());
+        let next_key = key(&next);
+
+        if let Some(&start) = seen.get(&next_key) {
+            let length = history.len() - start;
+            return (history, Some(Cycle { start, length }));
+        }
+
+        seen.insert(next_key, history.len());
+        history.push(next);
+    }
+
+    (history, None)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cycle::{find_cycle, find_cycle_by_key, nth_state, nth_state_by_key, Cycle};
+
+    fn step(x: &u64) -> u64 {
+        (x * x + 1) % 1000
+    }
+
+    fn brute_force(n: usize) -> u64 {
+        (0..n).fold(3, |x, _| step(&x))
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = find_cycle(3, step);
+
+        assert_eq!(
+            brute_force(cycle.start),
+            brute_force(cycle.start + cycle.length)
+        );
+        assert_ne!(
+            brute_force(cycle.start - 1),
+            brute_force(cycle.start + cycle.length - 1)
+        );
+
+        // fixed point
+        assert_eq!(
+            Cycle {
+                start: 5,
+                length: 1
+            },
+            find_cycle(0u32, |x| (x + 1).min(5))
+        );
+    }
+
+    #[test]
+    fn test_nth_state() {
+        for n in 0..200 {
+            assert_eq!(brute_force(n), nth_state(3, step, n));
+        }
+
+        let n = 1_000_000_000_000;
+        let cycle = find_cycle(3, step);
+        assert_eq!(brute_force(cycle.reduce(n)), nth_state(3, step, n));
+    }
+
+    #[test]
+    fn test_by_key() {
+        // the step counter doesn't influence the following states
+        let step = |(i, x): &(usize, u64)| (i + 1, (x * 7) % 10);
+
+        assert_eq!(
+            Cycle {
+                start: 0,
+                length: 4
+            },
+            find_cycle_by_key((0, 3), step, |&(_, x)| x)
+        );
+
+        // only x is right for step 1_000_000_000_001, the counter comes from step 1 which has
+        // the same x
+        assert_eq!(
+            (1, 1),
+            nth_state_by_key((0, 3), step, |&(_, x)| x, 1_000_000_000_001)
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
+/// Unsigned integer types the digit helpers work with
+pub trait Int:
+    Copy
+    + Eq
+    + Ord
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn from_u32(value: u32) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t {
+                const ZERO: Self = 0;
+                const ONE: Self = 1;
+
+                #[inline]
+                fn from_u32(value: u32) -> Self {
+                    value as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(u8, u16, u32, u64, u128, usize);
+
+/// Iterator over the digits of a number, most significant first, use `.rev()` to start with the
+/// least significant digit instead. Zero has a single digit.
+pub struct Digits<T: Int> {
+    n: T,
+    radix: T,
+    // radix to the power of (remaining digits - 1)
+    power: T,
+    len: usize,
+}
+
+impl<T: Int> Iterator for Digits<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let digit = self.n / self.power;
+        self.n = self.n % self.power;
+        self.power = self.power / self.radix;
+        self.len -= 1;
+
+        Some(digit)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.len, Some(self.len))
+    }
+}
+
+impl<T: Int> DoubleEndedIterator for Digits<T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.len == 0 {
+            return None;
+        }
+
+        let digit = self.n % self.radix;
+        self.n = self.n / self.radix;
+        self.power = self.power / self.radix;
+        self.len -= 1;
+
+        Some(digit)
+    }
+}
+
+impl<T: Int> ExactSizeIterator for Digits<T> {}
+
+fn radix<T: Int>(radix: u32) -> T {
+    assert!(radix >= 2, "radix must be at least 2 but is {radix}");
+    T::from_u32(radix)
+}
+
+fn pow<T: Int>(base: T, exp: usize) -> T {
+    (0..exp).fold(T::ONE, |acc, _| acc * base)
+}
+
+/// Digits of `n` in the given radix, most significant first
+pub fn digits<T: Int>(n: T, radix: u32) -> Digits<T> {
+    let len = num_digits(n, radix);
+    let radix = self::radix(radix);
+
+    Digits {
+        n,
+        radix,
+        power: pow(radix, len - 1),
+        len,
+    }
+}
+
+/// Number of digits of `n` in the given radix, zero has one digit
+pub fn num_digits<T: Int>(n: T, radix: u32) -> usize {
+    let radix = self::radix(radix);
+    let mut n = n / radix;
+    let mut count = 1;
+
+    while n != T::ZERO {
+        n = n / radix;
+        count += 1;
+    }
+
+    count
+}
+
+/// Builds a number from its digits, most significant first
+pub fn from_digits<T, D, I>(digits: I, radix: u32) -> T
+where
+    T: Int,
+    D: Into<T>,
+    I: IntoIterator<Item = D>,
+{
+    let radix = self::radix(radix);
+
+    digits
+        .into_iter()
+        .fold(T::ZERO, |acc, digit| acc * radix + digit.into())
+}
+
+/// Splits `n` into `parts` numbers with the same amount of digits, most significant first.
+/// Returns None if the digits can't be split evenly.
+pub fn split<T: Int>(n: T, parts: usize, radix: u32) -> Option<Vec<T>> {
+    let len = num_digits(n, radix);
+
+    if parts == 0 || !len.is_multiple_of(parts) {
+        return None;
+    }
+
+    // the divisor would have one digit more than n, which overflows at the type's maximum
+    if parts == 1 {
+        return Some(vec![n]);
+    }
+
+    let div = pow(self::radix(radix), len / parts);
+    let mut res = Vec::with_capacity(parts);
+    let mut n = n;
+
+    for _ in 1..parts {
+        res.push(n % div);
+        n = n / div;
+    }
+
+    res.push(n);
+    res.reverse();
+
+    Some(res)
+}
+
+/// Appends the digits of `b` to `a`, so concat(12, 34) is 1234
+pub fn concat<T: Int>(a: T, b: T, radix: u32) -> T {
+    a * pow(self::radix(radix), num_digits(b, radix)) + b
+}
+
+/// Checks if `n` consists of the same digit sequence repeated `times` times, e.g. 123123
+pub fn is_repeated<T: Int>(n: T, times: usize, radix: u32) -> bool {
+    match split(n, times, radix) {
+        Some(parts) => parts.windows(2).all(|w| w[0] == w[1]),
+        None => false,
+    }
+}
+
+/// Checks if `n` consists of any digit sequence repeated at least twice, e.g. 1111 or 121212
+pub fn has_repeated_pattern<T: Int>(n: T, radix: u32) -> bool {
+    (2..=num_digits(n, radix)).any(|times| is_repeated(n, times, radix))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::digits::{
+        concat, digits, from_digits, has_repeated_pattern, is_repeated, num_digits, split,
+    };
+
+    #[test]
+    fn test_digits() {
+        assert_eq!(vec![1, 2, 3], digits(123u32, 10).collect::<Vec<u32>>());
+        assert_eq!(
+            vec![3, 2, 1],
+            digits(123u32, 10).rev().collect::<Vec<u32>>()
+        );
+        assert_eq!(vec![0], digits(0u8, 10).collect::<Vec<u8>>());
+        assert_eq!(vec![1, 0, 1, 0], digits(10u64, 2).collect::<Vec<u64>>());
+        assert_eq!(vec![0xf, 0xf], digits(255u8, 16).collect::<Vec<u8>>());
+        assert_eq!(
+            vec![1, 8, 4, 4, 6, 7, 4, 4, 0, 7, 3, 7, 0, 9, 5, 5, 1, 6, 1, 5],
+            digits(u64::MAX, 10).collect::<Vec<u64>>()
+        );
+    }
+
+    #[test]
+    fn test_digits_both_ends() {
+        let mut it = digits(12345usize, 10);
+        assert_eq!(Some(1), it.next());
+        assert_eq!(Some(5), it.next_back());
+        assert_eq!(Some(2), it.next());
+        assert_eq!(Some(4), it.next_back());
+        assert_eq!(Some(3), it.next());
+        assert_eq!(None, it.next_back());
+        assert_eq!(None, it.next());
+    }
+
+    #[test]
+    fn test_num_digits() {
+        assert_eq!(1, num_digits(0u32, 10));
+        assert_eq!(1, num_digits(9u32, 10));
+        assert_eq!(2, num_digits(10u32, 10));
+        assert_eq!(20, num_digits(u64::MAX, 10));
+        assert_eq!(8, num_digits(255u8, 2));
+    }
+
+    #[test]
+    fn test_from_digits() {
+        assert_eq!(123usize, from_digits(vec![1usize, 2, 3], 10));
+        assert_eq!(
+            987654321111u64,
+            from_digits::<u64, _, _>([9u8, 8, 7, 6, 5, 4, 3, 2, 1, 1, 1, 1], 10)
+        );
+        assert_eq!(10u32, from_digits([1u32, 0, 1, 0], 2));
+        assert_eq!(0u32, from_digits(Vec::<u32>::new(), 10));
+    }
+
+    #[test]
+    fn test_split() {
+        assert_eq!(Some(vec![12, 34]), split(1234u32, 2, 10));
+        assert_eq!(Some(vec![1, 2, 3]), split(123u32, 3, 10));
+        assert_eq!(Some(vec![123]), split(123u32, 1, 10));
+        assert_eq!(Some(vec![10, 0]), split(1000u32, 2, 10));
+        assert_eq!(None, split(123u32, 2, 10));
+        assert_eq!(None, split(123u32, 0, 10));
+        assert_eq!(Some(vec![u64::MAX]), split(u64::MAX, 1, 10));
+        assert_eq!(Some(vec![200]), split(200u8, 1, 10));
+        assert_eq!(Some(vec![u8::MAX]), split(u8::MAX, 1, 2));
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(1234u32, concat(12, 34, 10));
+        assert_eq!(120u32, concat(12, 0, 10));
+        assert_eq!(0b1011u8, concat(0b10, 0b11, 2));
+    }
+
+    #[test]
+    fn test_repeated() {
+        assert!(is_repeated(1212u32, 2, 10));
+        assert!(is_repeated(111u32, 3, 10));
+        assert!(!is_repeated(111u32, 2, 10));
+        assert!(!is_repeated(1000u32, 2, 10));
+        assert!(is_repeated(u64::MAX, 1, 10));
+
+        assert!(has_repeated_pattern(111u32, 10));
+        assert!(has_repeated_pattern(824824824u64, 10));
+        assert!(!has_repeated_pattern(7u32, 10));
+        assert!(!has_repeated_pattern(1698522u64, 10));
+    }
+}
@@ -0,0 +1,132 @@
+/// Read access to a rectangular grid of cells
+pub trait Grid2D {
+    type Cell;
+
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell>;
+}
+
+impl<G: Grid2D> Grid2D for &G {
+    type Cell = G::Cell;
+
+    fn width(&self) -> usize {
+        (**self).width()
+    }
+
+    fn height(&self) -> usize {
+        (**self).height()
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        (**self).cell(x, y)
+    }
+}
+
+/// Adapter for grids stored as a flat `Vec<T>` in row order plus a width
+pub struct FlatGrid<'a, T> {
+    cells: &'a [T],
+    width: usize,
+}
+
+impl<'a, T> FlatGrid<'a, T> {
+    pub fn new(cells: &'a [T], width: usize) -> Self {
+        assert!(width > 0, "grid width must not be zero");
+        FlatGrid { cells, width }
+    }
+}
+
+impl<T: Clone> Grid2D for FlatGrid<'_, T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.cells.len().div_ceil(self.width)
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        if x >= self.width {
+            return None;
+        }
+
+        self.cells.get(y * self.width + x).cloned()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Connectivity {
+    /// up, down, left and right
+    Four,
+    /// including diagonals
+    Eight,
+    /// left and right, e.g. for runs of characters within a line
+    Horizontal,
+}
+
+impl Connectivity {
+    pub fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &[(0, -1), (-1, 0), (1, 0), (0, 1)],
+            Connectivity::Eight => &[
+                (-1, -1),
+                (0, -1),
+                (1, -1),
+                (-1, 0),
+                (1, 0),
+                (-1, 1),
+                (0, 1),
+                (1, 1),
+            ],
+            Connectivity::Horizontal => &[(-1, 0), (1, 0)],
+        }
+    }
+}
+
+/// Neighbor positions of (x, y) that lie within a width x height grid
+pub fn neighbors(
+    (x, y): (usize, usize),
+    width: usize,
+    height: usize,
+    connectivity: Connectivity,
+) -> impl Iterator<Item = (usize, usize)> {
+    connectivity.offsets().iter().filter_map(move |&(dx, dy)| {
+        let nx = x.checked_add_signed(dx)?;
+        let ny = y.checked_add_signed(dy)?;
+
+        (nx < width && ny < height).then_some((nx, ny))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{neighbors, Connectivity, FlatGrid, Grid2D};
+
+    #[test]
+    fn test_flat_grid() {
+        let cells = vec![1, 2, 3, 4, 5, 6];
+        let g = FlatGrid::new(&cells, 3);
+
+        assert_eq!(3, g.width());
+        assert_eq!(2, g.height());
+        assert_eq!(Some(6), g.cell(2, 1));
+        assert_eq!(None, g.cell(3, 0));
+        assert_eq!(None, g.cell(0, 2));
+    }
+
+    #[test]
+    fn test_neighbors() {
+        assert_eq!(
+            vec![(1, 0), (0, 1)],
+            neighbors((0, 0), 3, 3, Connectivity::Four).collect::<Vec<_>>()
+        );
+        assert_eq!(8, neighbors((1, 1), 3, 3, Connectivity::Eight).count());
+        assert_eq!(3, neighbors((2, 2), 3, 3, Connectivity::Eight).count());
+        assert_eq!(
+            vec![(0, 1), (2, 1)],
+            neighbors((1, 1), 3, 3, Connectivity::Horizontal).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use crate::grid::Grid2D;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+    pub const DARK_GRAY: Rgb = Rgb(40, 40, 40);
+    pub const RED: Rgb = Rgb(220, 50, 47);
+    pub const GREEN: Rgb = Rgb(80, 200, 80);
+    pub const YELLOW: Rgb = Rgb(250, 210, 40);
+    pub const BLUE: Rgb = Rgb(40, 110, 220);
+}
+
+/// RGB image that can be written as binary PPM or PNG
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize) -> Self {
+        Image {
+            width,
+            height,
+            pixels: vec![Rgb::BLACK; width * height],
+        }
+    }
+
+    /// Every grid cell becomes a `scale` x `scale` square colored by `palette`
+    pub fn from_grid<G, F>(grid: &G, scale: usize, mut palette: F) -> Self
+    where
+        G: Grid2D,
+        F: FnMut(usize, usize, G::Cell) -> Rgb,
+    {
+        assert!(scale > 0, "scale must not be zero");
+        let mut image = Image::new(grid.width() * scale, grid.height() * scale);
+
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let Some(cell) = grid.cell(x, y) else {
+                    continue;
+                };
+
+                let color = palette(x, y, cell);
+
+                for py in y * scale..(y + 1) * scale {
+                    image.pixels[py * image.width + x * scale..py * image.width + (x + 1) * scale]
+                        .fill(color);
+                }
+            }
+        }
+
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Rgb {
+        self.pixels[y * self.width + x]
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        self.pixels[y * self.width + x] = color;
+    }
+
+    /// Binary PPM (P6)
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+
+        for Rgb(r, g, b) in &self.pixels {
+            out.extend([r, g, b]);
+        }
+
+        out
+    }
+
+    /// 8 bit truecolor PNG, the image data isn't compressed
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+
+        let mut header = vec![];
+        header.extend((self.width as u32).to_be_bytes());
+        header.extend((self.height as u32).to_be_bytes());
+        // bit depth, color type truecolor, compression, filter, interlace
+        header.extend([8, 2, 0, 0, 0]);
+        png_chunk(&mut out, b"IHDR", &header);
+
+        // every scanline starts with filter type 0
+        let mut raw = Vec::with_capacity((self.width * 3 + 1) * self.height);
+        for row in self.pixels.chunks(self.width.max(1)).take(self.height) {
+            raw.push(0);
+
+            for Rgb(r, g, b) in row {
+                raw.extend([r, g, b]);
+            }
+        }
+
+        png_chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+        png_chunk(&mut out, b"IEND", &[]);
+
+        out
+    }
+
+    /// Picks the format based on the file extension
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+
+        let data = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("png") => self.to_png(),
+            Some("ppm") => self.to_ppm(),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("{} should end in .png or .ppm", path.display()),
+                ));
+            }
+        };
+
+        std::fs::write(path, data)
+    }
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+// zlib stream made of uncompressed deflate blocks
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = u16::MAX as usize;
+
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+
+        out.push(u8::from(last));
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+
+    out.extend(adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    const TABLE: [u32; 256] = {
+        let mut table = [0u32; 256];
+        let mut n = 0;
+
+        while n < 256 {
+            let mut c = n as u32;
+            let mut k = 0;
+
+            while k < 8 {
+                c = match c & 1 {
+                    1 => 0xedb88320 ^ (c >> 1),
+                    _ => c >> 1,
+                };
+                k += 1;
+            }
+
+            table[n] = c;
+            n += 1;
+        }
+
+        table
+    };
+
+    !data.iter().fold(u32::MAX, |crc, &byte| {
+        TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+
+    let (a, b) = data.iter().fold((1u32, 0u32), |(a, b), &byte| {
+        let a = (a + u32::from(byte)) % MOD;
+        (a, (b + a) % MOD)
+    });
+
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::FlatGrid;
+    use crate::image::{adler32, crc32, zlib_stored, Image, Rgb};
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(0xcbf43926, crc32(b"123456789"));
+        assert_eq!(0, crc32(b""));
+        assert_eq!(0x11e60398, adler32(b"Wikipedia"));
+        assert_eq!(1, adler32(b""));
+    }
+
+    #[test]
+    fn test_zlib_stored() {
+        let data = vec![7u8; 70_000];
+        let z = zlib_stored(&data);
+
+        // header, two blocks with 5 byte headers, data and checksum
+        assert_eq!(2 + 5 + 5 + 70_000 + 4, z.len());
+        assert_eq!([0, 0xff, 0xff, 0, 0], z[2..7]);
+        assert_eq!(1, z[2 + 5 + 65535]);
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let cells = vec![true, false, false, true];
+        let grid = FlatGrid::new(&cells, 2);
+        let image = Image::from_grid(&grid, 3, |_, _, on| match on {
+            true => Rgb::WHITE,
+            false => Rgb::BLACK,
+        });
+
+        assert_eq!((6, 6), (image.width(), image.height()));
+        assert_eq!(Rgb::WHITE, image.get(2, 2));
+        assert_eq!(Rgb::BLACK, image.get(3, 2));
+        assert_eq!(Rgb::WHITE, image.get(5, 5));
+
+        let ppm = image.to_ppm();
+        assert!(ppm.starts_with(b"P6\n6 6\n255\n"));
+        assert_eq!(11 + 6 * 6 * 3, ppm.len());
+    }
+
+    #[test]
+    fn test_png() {
+        let mut image = Image::new(2, 1);
+        image.set(1, 0, Rgb(1, 2, 3));
+        let png = image.to_png();
+
+        assert_eq!(b"\x89PNG\r\n\x1a\n", &png[..8]);
+        assert_eq!(b"IHDR", &png[12..16]);
+        assert_eq!([0, 0, 0, 2, 0, 0, 0, 1, 8, 2, 0, 0, 0], png[16..29]);
+        assert!(png.ends_with(&[0, 0, 0, 0, b'I', b'E', b'N', b'D', 0xae, 0x42, 0x60, 0x82]));
+
+        // uncompressed scanline inside the IDAT chunk
+        let scanline = [0, 0, 0, 0, 1, 2, 3];
+        assert!(png.windows(scanline.len()).any(|w| w == scanline));
+    }
+}
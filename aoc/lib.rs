@@ -0,0 +1,22 @@
+pub mod ansi;
+pub mod answer;
+pub mod args;
+pub mod bigint;
+pub mod bitgrid;
+pub mod blocks;
+pub mod cycle;
+pub mod digits;
+pub mod grid;
+pub mod image;
+pub mod json;
+pub mod matcher;
+pub mod memo;
+pub mod modint;
+pub mod ray;
+pub mod record;
+pub mod region;
+pub mod search;
+pub mod sim;
+pub mod sparse;
+pub mod transform;
+pub mod tui;
@@ -0,0 +1,202 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+/// Aho-Corasick automaton over bytes, finds every occurrence of a set of patterns in a single
+/// pass, overlapping ones included
+#[derive(Debug, Clone)]
+pub struct Matcher<V> {
+    // transition of every state for every byte, state 0 is the root
+    next: Vec<[u32; 256]>,
+    // patterns ending in a state, longest first, including the ones reached via fail links
+    outputs: Vec<Vec<usize>>,
+    // length and value of every pattern
+    patterns: Vec<(usize, V)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Match<'a, V> {
+    pub start: usize,
+    /// exclusive
+    pub end: usize,
+    pub value: &'a V,
+}
+
+// derives would require V: Copy
+impl<V> Clone for Match<'_, V> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<V> Copy for Match<'_, V> {}
+
+impl<V> Match<'_, V> {
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl<V> Matcher<V> {
+    pub fn new<P, I>(patterns: I) -> Self
+    where
+        P: AsRef<[u8]>,
+        I: IntoIterator<Item = (P, V)>,
+    {
+        let mut matcher = Matcher {
+            next: vec![[0; 256]],
+            outputs: vec![vec![]],
+            patterns: vec![],
+        };
+
+        for (pattern, value) in patterns {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "patterns must not be empty");
+
+            let mut state = 0;
+            for &byte in pattern {
+                state = match matcher.next[state][byte as usize] {
+                    0 => {
+                        matcher.next.push([0; 256]);
+                        matcher.outputs.push(vec![]);
+                        let child = matcher.next.len() - 1;
+                        matcher.next[state][byte as usize] = child as u32;
+                        child
+                    }
+                    child => child as usize,
+                };
+            }
+
+            matcher.outputs[state].push(matcher.patterns.len());
+            matcher.patterns.push((pattern.len(), value));
+        }
+
+        matcher.link();
+        matcher
+    }
+
+    // turns the trie into a full transition table, missing edges follow the fail links
+    fn link(&mut self) {
+        let mut fail = vec![0; self.next.len()];
+        let mut queue = VecDeque::from([0]);
+
+        while let Some(state) = queue.pop_front() {
+            for byte in 0..256 {
+                let child = self.next[state][byte] as usize;
+                let fallback = match state {
+                    0 => 0,
+                    _ => self.next[fail[state]][byte],
+                };
+
+                // children of the root that don't exist yet loop back to it
+                if child == 0 {
+                    self.next[state][byte] = fallback;
+                    continue;
+                }
+
+                fail[child] = fallback as usize;
+                let inherited = self.outputs[fail[child]].clone();
+                self.outputs[child].extend(inherited);
+                queue.push_back(child);
+            }
+        }
+    }
+
+    /// All matches in the order they end, matches ending at the same byte longest first
+    pub fn find_overlapping<'a>(
+        &'a self,
+        haystack: &'a [u8],
+    ) -> impl Iterator<Item = Match<'a, V>> + 'a {
+        haystack
+            .iter()
+            .enumerate()
+            .scan(0, move |state, (i, &byte)| {
+                *state = self.next[*state][byte as usize] as usize;
+                Some((i + 1, *state))
+            })
+            .flat_map(move |(end, state)| {
+                self.outputs[state].iter().map(move |&pattern| {
+                    let (len, value) = &self.patterns[pattern];
+
+                    Match {
+                        start: end - len,
+                        end,
+                        value,
+                    }
+                })
+            })
+    }
+
+    /// Leftmost and rightmost match, the longer one if several start at the same position
+    pub fn first_and_last<'a>(
+        &'a self,
+        haystack: &'a [u8],
+    ) -> Option<(Match<'a, V>, Match<'a, V>)> {
+        let mut found: Option<(Match<V>, Match<V>)> = None;
+
+        for m in self.find_overlapping(haystack) {
+            let Some((first, last)) = &mut found else {
+                found = Some((m, m));
+                continue;
+            };
+
+            if (m.start, Reverse(m.len())) < (first.start, Reverse(first.len())) {
+                *first = m;
+            }
+
+            if (m.start, m.len()) > (last.start, last.len()) {
+                *last = m;
+            }
+        }
+
+        found
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::matcher::Matcher;
+
+    fn matches(matcher: &Matcher<u32>, haystack: &str) -> Vec<(usize, usize, u32)> {
+        matcher
+            .find_overlapping(haystack.as_bytes())
+            .map(|m| (m.start, m.end, *m.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let matcher = Matcher::new([("one", 1), ("two", 2), ("eight", 8), ("1", 1)]);
+
+        assert_eq!(vec![(0, 5, 8), (4, 7, 2)], matches(&matcher, "eightwo"));
+        assert_eq!(vec![(1, 4, 2), (3, 6, 1)], matches(&matcher, "xtwone"));
+        assert_eq!(vec![(2, 3, 1), (5, 8, 1)], matches(&matcher, "oo1onone"));
+        assert!(matches(&matcher, "on tw eigh").is_empty());
+    }
+
+    #[test]
+    fn test_nested_patterns() {
+        let matcher = Matcher::new([("he", 0), ("she", 1), ("hers", 2), ("his", 3), ("e", 4)]);
+
+        assert_eq!(
+            vec![(1, 4, 1), (2, 4, 0), (3, 4, 4), (2, 6, 2)],
+            matches(&matcher, "ushers")
+        );
+    }
+
+    #[test]
+    fn test_first_and_last() {
+        let matcher = Matcher::new([("seven", 7), ("seventeen", 17), ("teen", 0), ("n", 5)]);
+
+        let (first, last) = matcher.first_and_last(b"xseventeen").unwrap();
+        assert_eq!((1, 10, 17), (first.start, first.end, *first.value));
+        assert_eq!((9, 10, 5), (last.start, last.end, *last.value));
+
+        let (first, last) = matcher.first_and_last(b"a n").unwrap();
+        assert_eq!(first, last);
+        assert_eq!(None, matcher.first_and_last(b"eleve"));
+    }
+}
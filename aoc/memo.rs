@@ -0,0 +1,170 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::Hash;
+
+/// Cache for recursive solvers
+///
+/// `get_or_compute` hands the memo back to the compute function so it can recurse into itself,
+/// `get_or_compute_iter` does the same with an explicit stack for inputs that are too deep for
+/// the call stack.
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K: Hash + Eq + Clone, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo {
+            cache: HashMap::new(),
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Returns the cached value for `key` or computes and caches it
+    pub fn get_or_compute<F>(&mut self, key: K, compute: F) -> &V
+    where
+        F: FnOnce(&mut Self) -> V,
+    {
+        if !self.cache.contains_key(&key) {
+            let value = compute(self);
+            self.cache.insert(key.clone(), value);
+        }
+
+        &self.cache[&key]
+    }
+
+    /// Same as `get_or_compute` but without recursion, `deps` lists the keys a value depends on
+    /// and `combine` builds the value once all of them are known (in the same order).
+    ///
+    /// Panics if the dependencies contain a cycle.
+    pub fn get_or_compute_iter<D, C>(&mut self, key: K, mut deps: D, mut combine: C) -> &V
+    where
+        D: FnMut(&K) -> Vec<K>,
+        C: FnMut(&K, &[&V]) -> V,
+    {
+        let mut in_progress = HashSet::new();
+        let mut stack: Vec<(K, Option<Vec<K>>)> = vec![(key.clone(), None)];
+
+        while let Some((current, current_deps)) = stack.pop() {
+            if self.cache.contains_key(&current) {
+                continue;
+            }
+
+            match current_deps {
+                // all dependencies are resolved now
+                Some(current_deps) => {
+                    let values = current_deps
+                        .iter()
+                        .map(|dep| &self.cache[dep])
+                        .collect::<Vec<&V>>();
+                    let value = combine(&current, &values);
+
+                    in_progress.remove(&current);
+                    self.cache.insert(current, value);
+                }
+                None => {
+                    assert!(
+                        in_progress.insert(current.clone()),
+                        "dependency cycle detected"
+                    );
+
+                    let current_deps = deps(&current);
+                    let missing = current_deps
+                        .iter()
+                        .filter(|dep| !self.cache.contains_key(dep))
+                        .cloned()
+                        .collect::<Vec<K>>();
+
+                    stack.push((current, Some(current_deps)));
+                    stack.extend(missing.into_iter().map(|dep| (dep, None)));
+                }
+            }
+        }
+
+        &self.cache[&key]
+    }
+}
+
+impl<K: Hash + Eq + Clone, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::memo::Memo;
+
+    fn fib(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        if n < 2 {
+            return n;
+        }
+
+        let a = *memo.get_or_compute(n - 1, |memo| fib(n - 1, memo));
+        let b = *memo.get_or_compute(n - 2, |memo| fib(n - 2, memo));
+
+        a + b
+    }
+
+    #[test]
+    fn test_get_or_compute() {
+        let mut memo = Memo::new();
+        assert_eq!(12586269025, fib(50, &mut memo));
+        assert_eq!(50, memo.len());
+    }
+
+    #[test]
+    fn test_get_or_compute_cached() {
+        let mut memo = Memo::new();
+        assert_eq!(1, *memo.get_or_compute("a", |_| 1));
+        assert_eq!(1, *memo.get_or_compute("a", |_| 2));
+    }
+
+    #[test]
+    fn test_get_or_compute_iter() {
+        let mut memo: Memo<u64, u64> = Memo::new();
+
+        // deep enough to blow the stack when done recursively
+        let res = *memo.get_or_compute_iter(
+            100_000,
+            |n| if *n == 0 { vec![] } else { vec![n - 1] },
+            |n, deps| match deps {
+                [prev] => (**prev + n) % 1_000_007,
+                _ => 0,
+            },
+        );
+
+        assert_eq!((100_000 * 100_001 / 2) % 1_000_007, res);
+    }
+
+    #[test]
+    fn test_get_or_compute_iter_shared_deps() {
+        let mut memo: Memo<u64, u64> = Memo::new();
+        let res = *memo.get_or_compute_iter(
+            90,
+            |n| if *n < 2 { vec![] } else { vec![n - 1, n - 2] },
+            |n, deps| match deps {
+                [a, b] => **a + **b,
+                _ => *n,
+            },
+        );
+
+        assert_eq!(2880067194370816120, res);
+    }
+
+    #[test]
+    #[should_panic(expected = "dependency cycle detected")]
+    fn test_get_or_compute_iter_cycle() {
+        let mut memo: Memo<u64, u64> = Memo::new();
+        memo.get_or_compute_iter(0, |n| vec![(n + 1) % 3], |_, _| 0);
+    }
+}
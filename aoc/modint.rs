@@ -0,0 +1,252 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+/// Integer modulo `N`, all arithmetic wraps around
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ModInt<const N: u64> {
+    value: u64,
+}
+
+impl<const N: u64> ModInt<N> {
+    pub const ZERO: Self = ModInt { value: 0 };
+
+    pub fn new(value: u64) -> Self {
+        assert!(N > 0, "modulus must not be zero");
+        ModInt { value: value % N }
+    }
+
+    pub fn from_signed(value: i64) -> Self {
+        Self::new(i128::from(value).rem_euclid(i128::from(N)) as u64)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn add_signed(self, delta: i64) -> Self {
+        self + Self::from_signed(delta)
+    }
+
+    /// Moves `delta` single steps (backwards if negative) and counts how often it lands on
+    /// `residue` along the way, the starting value doesn't count
+    pub fn add_counting(self, delta: i64, residue: u64) -> (Self, u64) {
+        let residue = Self::new(residue);
+        let steps = delta.unsigned_abs();
+
+        // steps needed until the first hit
+        let first = match delta >= 0 {
+            true => (residue - self).value,
+            false => (self - residue).value,
+        };
+        let first = if first == 0 { N } else { first };
+
+        let count = match steps >= first {
+            true => (steps - first) / N + 1,
+            false => 0,
+        };
+
+        (self.add_signed(delta), count)
+    }
+
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut res = Self::new(1);
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res *= base;
+            }
+
+            base *= base;
+            exp >>= 1;
+        }
+
+        res
+    }
+
+    /// Multiplicative inverse, only exists if the value and `N` are coprime
+    pub fn inverse(self) -> Option<Self> {
+        let (gcd, x, _) = extended_gcd(i128::from(self.value), i128::from(N));
+
+        if gcd != 1 {
+            return None;
+        }
+
+        Some(Self::new(x.rem_euclid(i128::from(N)) as u64))
+    }
+}
+
+impl<const N: u64> From<u64> for ModInt<N> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const N: u64> fmt::Display for ModInt<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl<const N: u64> Add for ModInt<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        ModInt {
+            value: ((u128::from(self.value) + u128::from(rhs.value)) % u128::from(N)) as u64,
+        }
+    }
+}
+
+impl<const N: u64> Sub for ModInt<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        self + -rhs
+    }
+}
+
+impl<const N: u64> Neg for ModInt<N> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        ModInt {
+            value: (N - self.value) % N,
+        }
+    }
+}
+
+impl<const N: u64> Mul for ModInt<N> {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        ModInt {
+            value: ((u128::from(self.value) * u128::from(rhs.value)) % u128::from(N)) as u64,
+        }
+    }
+}
+
+impl<const N: u64> AddAssign for ModInt<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const N: u64> SubAssign for ModInt<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const N: u64> MulAssign for ModInt<N> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+// returns (gcd, x, y) with a * x + b * y = gcd
+fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a, 1, 0);
+    }
+
+    let (gcd, x, y) = extended_gcd(b, a % b);
+    (gcd, y, x - (a / b) * y)
+}
+
+/// Chinese remainder theorem for `(residue, modulus)` pairs, the moduli don't have to be
+/// coprime. Returns the smallest solution and the combined modulus, None if there is none.
+pub fn crt(congruences: &[(u64, u64)]) -> Option<(u64, u64)> {
+    let mut res: (i128, i128) = (0, 1);
+
+    for &(residue, modulus) in congruences {
+        let (residue, modulus) = (i128::from(residue), i128::from(modulus));
+        let (gcd, x, _) = extended_gcd(res.1, modulus);
+
+        if (residue - res.0) % gcd != 0 {
+            return None;
+        }
+
+        let lcm = res.1 / gcd * modulus;
+        let k = ((residue - res.0) / gcd * x).rem_euclid(modulus / gcd);
+        res = ((res.0 + res.1 * k).rem_euclid(lcm), lcm);
+    }
+
+    Some((u64::try_from(res.0).ok()?, u64::try_from(res.1).ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::modint::{crt, ModInt};
+
+    type M7 = ModInt<7>;
+
+    #[test]
+    fn test_arithmetic() {
+        assert_eq!(M7::new(1), M7::new(5) + M7::new(3));
+        assert_eq!(M7::new(5), M7::new(1) - M7::new(3));
+        assert_eq!(M7::new(1), M7::new(4) * M7::new(2));
+        assert_eq!(M7::new(4), -M7::new(3));
+        assert_eq!(M7::ZERO, -M7::ZERO);
+        assert_eq!(M7::new(6), M7::from_signed(-8));
+        assert_eq!(M7::new(2), M7::new(5).add_signed(-10));
+        assert_eq!(3, M7::new(10).value());
+    }
+
+    #[test]
+    fn test_large_modulus() {
+        type Big = ModInt<{ u64::MAX - 58 }>;
+        let a = Big::new(u64::MAX - 60);
+
+        assert_eq!(u64::MAX - 62, (a + a).value());
+        assert_eq!(4, (a * a).value());
+    }
+
+    #[test]
+    fn test_pow_inverse() {
+        assert_eq!(M7::new(1), M7::new(3).pow(6));
+        assert_eq!(M7::new(1), M7::new(3).pow(0));
+        assert_eq!(Some(M7::new(5)), M7::new(3).inverse());
+        assert_eq!(None, M7::ZERO.inverse());
+        assert_eq!(None, ModInt::<100>::new(10).inverse());
+
+        for v in 1..7 {
+            assert_eq!(M7::new(1), M7::new(v) * M7::new(v).inverse().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_add_counting() {
+        type Dial = ModInt<100>;
+
+        assert_eq!((Dial::new(50), 10), Dial::new(50).add_counting(1000, 0));
+        assert_eq!((Dial::new(0), 1), Dial::new(50).add_counting(-50, 0));
+        assert_eq!((Dial::new(0), 0), Dial::new(0).add_counting(0, 0));
+        assert_eq!((Dial::new(99), 0), Dial::new(0).add_counting(-1, 0));
+        assert_eq!((Dial::new(0), 2), Dial::new(0).add_counting(-200, 0));
+        assert_eq!((Dial::new(15), 2), Dial::new(95).add_counting(120, 10));
+
+        // compare with single steps
+        for start in 0..100 {
+            for delta in -250i64..250 {
+                let mut dial = Dial::new(start);
+                let mut count = 0;
+
+                for _ in 0..delta.abs() {
+                    dial = dial.add_signed(delta.signum());
+                    count += u64::from(dial.value() == 42);
+                }
+
+                assert_eq!((dial, count), Dial::new(start).add_counting(delta, 42));
+            }
+        }
+    }
+
+    #[test]
+    fn test_crt() {
+        assert_eq!(Some((23, 105)), crt(&[(2, 3), (3, 5), (2, 7)]));
+        assert_eq!(Some((10, 12)), crt(&[(4, 6), (2, 4)]));
+        assert_eq!(None, crt(&[(1, 4), (2, 6)]));
+        assert_eq!(Some((0, 1)), crt(&[]));
+    }
+}
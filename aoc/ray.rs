@@ -0,0 +1,274 @@
+use crate::grid::Grid2D;
+use std::collections::HashSet;
+
+/// Direction of travel, y grows downwards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir {
+    pub const ALL: [Dir; 4] = [Dir::Up, Dir::Right, Dir::Down, Dir::Left];
+
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Dir::Up => (0, -1),
+            Dir::Right => (1, 0),
+            Dir::Down => (0, 1),
+            Dir::Left => (-1, 0),
+        }
+    }
+
+    /// Clockwise
+    pub fn turn_right(self) -> Dir {
+        match self {
+            Dir::Up => Dir::Right,
+            Dir::Right => Dir::Down,
+            Dir::Down => Dir::Left,
+            Dir::Left => Dir::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Dir {
+        self.turn_right().reverse()
+    }
+
+    pub fn reverse(self) -> Dir {
+        self.turn_right().turn_right()
+    }
+}
+
+/// Position plus direction of travel
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ray {
+    pub pos: (usize, usize),
+    pub dir: Dir,
+}
+
+impl Ray {
+    pub fn new(pos: (usize, usize), dir: Dir) -> Self {
+        Ray { pos, dir }
+    }
+
+    pub fn with_dir(self, dir: Dir) -> Self {
+        Ray { dir, ..self }
+    }
+
+    /// The ray one cell further, None if that leaves a width x height grid
+    pub fn advance(self, width: usize, height: usize) -> Option<Ray> {
+        let (dx, dy) = self.dir.offset();
+        let x = self.pos.0.checked_add_signed(dx).filter(|x| *x < width)?;
+        let y = self.pos.1.checked_add_signed(dy).filter(|y| *y < height)?;
+
+        Some(Ray {
+            pos: (x, y),
+            ..self
+        })
+    }
+
+    /// Cells the ray passes until it leaves the grid, the start isn't included. Combine with
+    /// `take_while` for line of sight checks.
+    pub fn path(self, width: usize, height: usize) -> impl Iterator<Item = (usize, usize)> {
+        std::iter::successors(self.advance(width, height), move |ray| {
+            ray.advance(width, height)
+        })
+        .map(|ray| ray.pos)
+    }
+}
+
+/// What happens to a ray entering a cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Hit {
+    /// keeps going
+    Pass,
+    /// stops in this cell
+    Absorb,
+    /// keeps going in a new direction
+    Reflect(Dir),
+    /// stops in this cell and the given rays start from their own positions
+    Split(Vec<Ray>),
+}
+
+/// Rays moving through a grid one cell per step. Every position and direction is only entered
+/// once, so rays that merge continue as one and loops die out.
+#[derive(Debug, Clone)]
+pub struct Beams {
+    rays: Vec<Ray>,
+    seen: HashSet<Ray>,
+}
+
+impl Beams {
+    pub fn new(start: Ray) -> Self {
+        Beams {
+            rays: vec![start],
+            seen: HashSet::from([start]),
+        }
+    }
+
+    /// Rays that are still moving
+    pub fn rays(&self) -> &[Ray] {
+        &self.rays
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.rays.is_empty()
+    }
+
+    /// Every state a ray has been in, including the cells where it got absorbed or split
+    pub fn seen(&self) -> &HashSet<Ray> {
+        &self.seen
+    }
+
+    /// Cells any ray has been in
+    pub fn energized(&self) -> HashSet<(usize, usize)> {
+        self.seen.iter().map(|ray| ray.pos).collect()
+    }
+
+    /// Moves every ray one cell forward and lets `interact` decide what happens to it there,
+    /// false once no ray is left
+    pub fn step<G, F>(&mut self, grid: &G, mut interact: F) -> bool
+    where
+        G: Grid2D,
+        F: FnMut(Ray, G::Cell) -> Hit,
+    {
+        let mut next = vec![];
+
+        for ray in std::mem::take(&mut self.rays) {
+            let Some(ray) = ray.advance(grid.width(), grid.height()) else {
+                continue;
+            };
+
+            let Some(cell) = grid.cell(ray.pos.0, ray.pos.1) else {
+                continue;
+            };
+
+            if !self.seen.insert(ray) {
+                continue;
+            }
+
+            match interact(ray, cell) {
+                Hit::Pass => next.push(ray),
+                Hit::Absorb => {}
+                Hit::Reflect(dir) => {
+                    if self.seen.insert(ray.with_dir(dir)) {
+                        next.push(ray.with_dir(dir));
+                    }
+                }
+                Hit::Split(rays) => {
+                    next.extend(rays.into_iter().filter(|ray| self.seen.insert(*ray)));
+                }
+            }
+        }
+
+        self.rays = next;
+        !self.rays.is_empty()
+    }
+}
+
+/// Follows `start` and every ray split off from it until all of them left the grid, got
+/// absorbed or reached a state seen before
+pub fn cast<G, F>(grid: &G, start: Ray, mut interact: F) -> Beams
+where
+    G: Grid2D,
+    F: FnMut(Ray, G::Cell) -> Hit,
+{
+    let mut beams = Beams::new(start);
+    while beams.step(grid, &mut interact) {}
+
+    beams
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::FlatGrid;
+    use crate::ray::{cast, Beams, Dir, Hit, Ray};
+
+    fn grid(text: &str) -> (Vec<char>, usize) {
+        let width = text.lines().next().unwrap().len();
+        (text.lines().flat_map(|line| line.chars()).collect(), width)
+    }
+
+    // mirrors and splitters as in 2023 day 16
+    fn contraption(ray: Ray, cell: char) -> Hit {
+        match (cell, ray.dir) {
+            ('/', Dir::Up | Dir::Down) => Hit::Reflect(ray.dir.turn_right()),
+            ('/', Dir::Left | Dir::Right) => Hit::Reflect(ray.dir.turn_left()),
+            ('\\', Dir::Up | Dir::Down) => Hit::Reflect(ray.dir.turn_left()),
+            ('\\', Dir::Left | Dir::Right) => Hit::Reflect(ray.dir.turn_right()),
+            ('|', Dir::Left | Dir::Right) => {
+                Hit::Split(vec![ray.with_dir(Dir::Up), ray.with_dir(Dir::Down)])
+            }
+            ('-', Dir::Up | Dir::Down) => {
+                Hit::Split(vec![ray.with_dir(Dir::Left), ray.with_dir(Dir::Right)])
+            }
+            ('#', _) => Hit::Absorb,
+            _ => Hit::Pass,
+        }
+    }
+
+    #[test]
+    fn test_dir() {
+        assert_eq!(Dir::Left, Dir::Up.turn_left());
+        assert_eq!(Dir::Up, Dir::Left.turn_right());
+        assert_eq!(Dir::Up, Dir::Down.reverse());
+        assert!(Dir::ALL.iter().all(|d| d.turn_left().turn_right() == *d));
+    }
+
+    #[test]
+    fn test_path() {
+        let ray = Ray::new((1, 1), Dir::Right);
+        assert_eq!(vec![(2, 1), (3, 1)], ray.path(4, 3).collect::<Vec<_>>());
+        assert_eq!(0, Ray::new((0, 0), Dir::Up).path(4, 3).count());
+        assert_eq!(None, Ray::new((0, 2), Dir::Down).advance(4, 3));
+    }
+
+    #[test]
+    fn test_cast() {
+        let (cells, width) = grid(
+            r".|...\....
+|.-.\.....
+.....|-...
+........|.
+..........
+.........\
+..../.\\..
+.-.-/..|..
+.|....-|.\
+..//.|....",
+        );
+        let grid = FlatGrid::new(&cells, width);
+
+        // the ray enters the grid from the left
+        let beams = cast(&grid, Ray::new((0, 0), Dir::Right), contraption);
+        assert!(beams.is_done());
+        assert_eq!(46, beams.energized().len());
+    }
+
+    #[test]
+    fn test_loops_and_absorb() {
+        let (cells, width) = grid(
+            r"/..\
+....
+\../
+#...",
+        );
+        let grid = FlatGrid::new(&cells, width);
+
+        // circles forever without dedup
+        let mut beams = Beams::new(Ray::new((1, 0), Dir::Right));
+        let mut steps = 0;
+        while beams.step(&grid, contraption) {
+            steps += 1;
+        }
+        assert_eq!(9, steps);
+        assert_eq!(10, beams.energized().len());
+
+        let beams = cast(&grid, Ray::new((3, 3), Dir::Left), contraption);
+        let mut energized = beams.energized().into_iter().collect::<Vec<_>>();
+        energized.sort();
+        assert_eq!(vec![(0, 3), (1, 3), (2, 3), (3, 3)], energized);
+    }
+}
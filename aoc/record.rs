@@ -0,0 +1,396 @@
+use crate::image::{Image, Rgb};
+use crate::sim::{Driver, Simulation};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GifError {
+    NoFrames,
+    TooLarge { width: usize, height: usize },
+    SizeMismatch { frame: usize },
+    TooManyColors,
+}
+
+impl fmt::Display for GifError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GifError::NoFrames => write!(f, "there are no frames to write"),
+            GifError::TooLarge { width, height } => {
+                write!(f, "{width}x{height} exceeds the maximum gif size")
+            }
+            GifError::SizeMismatch { frame } => {
+                write!(f, "frame {frame} has a different size than the first one")
+            }
+            GifError::TooManyColors => write!(f, "gifs can't have more than 256 colors"),
+        }
+    }
+}
+
+impl Error for GifError {}
+
+/// Collects frames of a simulation and writes them as animated GIF or numbered PNGs
+#[derive(Debug, Clone)]
+pub struct Recorder {
+    frames: Vec<Image>,
+    fps: u32,
+}
+
+impl Recorder {
+    pub fn new(fps: u32) -> Self {
+        assert!(fps > 0, "fps must not be zero");
+        Recorder {
+            frames: vec![],
+            fps,
+        }
+    }
+
+    pub fn push(&mut self, frame: Image) {
+        self.frames.push(frame);
+    }
+
+    pub fn frames(&self) -> &[Image] {
+        &self.frames
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Looping GIF with a single palette shared by all frames
+    pub fn to_gif(&self) -> Result<Vec<u8>, GifError> {
+        let first = self.frames.first().ok_or(GifError::NoFrames)?;
+        let (width, height) = (first.width(), first.height());
+
+        let (Ok(w), Ok(h)) = (u16::try_from(width), u16::try_from(height)) else {
+            return Err(GifError::TooLarge { width, height });
+        };
+
+        if let Some(frame) = self
+            .frames
+            .iter()
+            .position(|f| (f.width(), f.height()) != (width, height))
+        {
+            return Err(GifError::SizeMismatch { frame });
+        }
+
+        let mut palette = vec![];
+        let mut index = HashMap::new();
+        let mut indexed = vec![];
+
+        for frame in &self.frames {
+            let mut pixels = Vec::with_capacity(width * height);
+
+            for y in 0..height {
+                for x in 0..width {
+                    let color = frame.get(x, y);
+                    let i = *index.entry(color).or_insert_with(|| {
+                        palette.push(color);
+                        palette.len() - 1
+                    });
+
+                    pixels.push(u8::try_from(i).map_err(|_| GifError::TooManyColors)?);
+                }
+            }
+
+            indexed.push(pixels);
+        }
+
+        // the color table has 2^bits entries, lzw needs at least 2 bits
+        let bits = (palette.len().max(2) as u32 - 1).ilog2() as u8 + 1;
+        let min_code_size = bits.max(2);
+        let delay = (100 / self.fps).max(1) as u16;
+
+        let mut out = b"GIF89a".to_vec();
+        out.extend(w.to_le_bytes());
+        out.extend(h.to_le_bytes());
+        out.extend([0xf0 | (bits - 1), 0, 0]);
+
+        palette.resize(1 << bits, Rgb::BLACK);
+        for Rgb(r, g, b) in palette {
+            out.extend([r, g, b]);
+        }
+
+        // loop forever
+        out.extend(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+
+        for pixels in indexed {
+            out.extend([0x21, 0xf9, 0x04, 0x00]);
+            out.extend(delay.to_le_bytes());
+            out.extend([0x00, 0x00]);
+
+            out.extend([0x2c, 0, 0, 0, 0]);
+            out.extend(w.to_le_bytes());
+            out.extend(h.to_le_bytes());
+            out.push(0);
+
+            out.push(min_code_size);
+            for block in lzw_encode(&pixels, min_code_size).chunks(255) {
+                out.push(block.len() as u8);
+                out.extend(block);
+            }
+            out.push(0);
+        }
+
+        out.push(0x3b);
+        Ok(out)
+    }
+
+    pub fn save_gif<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        std::fs::write(path, self.to_gif().map_err(io::Error::other)?)
+    }
+
+    /// Writes `frame_00000.png`, `frame_00001.png`, ... into `dir`
+    pub fn save_png_sequence<P: AsRef<Path>>(&self, dir: P) -> io::Result<()> {
+        std::fs::create_dir_all(&dir)?;
+
+        for (i, frame) in self.frames.iter().enumerate() {
+            frame.save(dir.as_ref().join(format!("frame_{i:05}.png")))?;
+        }
+
+        Ok(())
+    }
+
+    /// GIF if the path ends in `.gif`, otherwise a PNG sequence in that directory
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("gif") => self.save_gif(path),
+            _ => self.save_png_sequence(path),
+        }
+    }
+}
+
+/// Records the initial state and the state after every step until the simulation is done or
+/// `max_frames` are captured
+pub fn record<S, R>(sim: &mut S, mut render: R, fps: u32, max_frames: usize) -> Recorder
+where
+    S: Simulation,
+    R: FnMut(&S) -> Image,
+{
+    let mut recorder = Recorder::new(fps);
+
+    if max_frames == 0 {
+        return recorder;
+    }
+
+    recorder.push(render(sim));
+
+    Driver::new()
+        .max_steps(max_frames - 1)
+        .observe(|_, sim: &S| recorder.push(render(sim)))
+        .run(sim);
+
+    recorder
+}
+
+// variable width lzw as used by gif, codes are packed starting at the least significant bit
+fn lzw_encode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+    const MAX_CODES: u16 = 4096;
+
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+
+    let mut out = vec![];
+    let mut acc = 0u32;
+    let mut acc_bits = 0;
+    let mut write = |code: u16, size: u8| {
+        acc |= u32::from(code) << acc_bits;
+        acc_bits += size;
+
+        while acc_bits >= 8 {
+            out.push(acc as u8);
+            acc >>= 8;
+            acc_bits -= 8;
+        }
+    };
+
+    let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut size = min_code_size + 1;
+    let mut prefix = None;
+
+    write(clear, size);
+
+    for &byte in data {
+        let Some(current) = prefix else {
+            prefix = Some(u16::from(byte));
+            continue;
+        };
+
+        if let Some(&code) = table.get(&(current, byte)) {
+            prefix = Some(code);
+            continue;
+        }
+
+        write(current, size);
+
+        if next == MAX_CODES {
+            write(clear, size);
+            table.clear();
+            next = end + 1;
+            size = min_code_size + 1;
+        } else {
+            table.insert((current, byte), next);
+            next += 1;
+
+            // the decoder lags one code behind, so it widens once `next` passes the limit
+            if next > 1 << size && size < 12 {
+                size += 1;
+            }
+        }
+
+        prefix = Some(u16::from(byte));
+    }
+
+    if let Some(current) = prefix {
+        write(current, size);
+    }
+
+    write(end, size);
+    write(0, 7);
+
+    out
+}
+
+#[cfg(test)]
+mod test {
+    use crate::image::{Image, Rgb};
+    use crate::record::{lzw_encode, record, GifError, Recorder};
+    use crate::sim::Simulation;
+
+    // straightforward gif lzw decoder to check the encoder against
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1usize << min_code_size;
+        let end = clear + 1;
+
+        let mut table: Vec<Vec<u8>> = vec![];
+        let mut size = min_code_size + 1;
+        let mut prev: Option<usize> = None;
+        let mut out = vec![];
+        let mut pos = 0;
+
+        loop {
+            let code = (0..size as usize).fold(0, |acc, i| {
+                let bit = (data[(pos + i) / 8] >> ((pos + i) % 8)) & 1;
+                acc | (bit as usize) << i
+            });
+            pos += size as usize;
+
+            if code == clear {
+                table = (0..clear).map(|i| vec![i as u8]).collect();
+                table.extend([vec![], vec![]]);
+                size = min_code_size + 1;
+                prev = None;
+                continue;
+            }
+
+            if code == end {
+                return out;
+            }
+
+            let entry = match (table.get(code), prev) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(p)) => {
+                    let mut entry = table[p].clone();
+                    entry.push(table[p][0]);
+                    entry
+                }
+                (None, None) => panic!("invalid code {code}"),
+            };
+
+            if let Some(p) = prev
+                && table.len() < 4096
+            {
+                let mut new = table[p].clone();
+                new.push(entry[0]);
+                table.push(new);
+
+                if table.len() == 1 << size && size < 12 {
+                    size += 1;
+                }
+            }
+
+            out.extend(&entry);
+            prev = Some(code);
+        }
+    }
+
+    #[test]
+    fn test_lzw_round_trip() {
+        let inputs = [
+            vec![],
+            vec![0],
+            vec![1, 1, 1, 1, 1, 1, 1, 1, 1, 1],
+            (0..10_000).map(|i| (i * i % 7) as u8).collect::<Vec<_>>(),
+            // enough distinct sequences to fill the table and force a clear code
+            (0..100_000u32)
+                .map(|i| (i.wrapping_mul(2_654_435_761) >> 29) as u8)
+                .collect(),
+        ];
+
+        for data in inputs {
+            for min_code_size in [2, 3, 8] {
+                let data = data
+                    .iter()
+                    .map(|b| b & ((1u16 << min_code_size) - 1) as u8)
+                    .collect::<Vec<_>>();
+                let encoded = lzw_encode(&data, min_code_size);
+                assert_eq!(data, lzw_decode(&encoded, min_code_size));
+            }
+        }
+    }
+
+    // counts up to 4
+    struct Counter(u32);
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.0 == 4 {
+                return false;
+            }
+
+            self.0 += 1;
+            true
+        }
+    }
+
+    #[test]
+    fn test_gif() {
+        let render = |c: &Counter| {
+            let mut image = Image::new(4, 2);
+            image.set(c.0 as usize % 4, 1, Rgb::RED);
+            image
+        };
+        let recorder = record(&mut Counter(0), render, 10, 100);
+
+        assert_eq!(5, recorder.len());
+        let gif = recorder.to_gif().unwrap();
+
+        assert_eq!(b"GIF89a", &gif[..6]);
+        assert_eq!([4, 0, 2, 0, 0xf0], gif[6..11]);
+        // two colors, black first
+        assert_eq!([0, 0, 0, 220, 50, 47], gif[13..19]);
+        assert_eq!(
+            5,
+            gif.windows(3).filter(|w| *w == [0x21, 0xf9, 0x04]).count()
+        );
+        assert_eq!(Some(&0x3b), gif.last());
+
+        assert_eq!(Err(GifError::NoFrames), Recorder::new(1).to_gif());
+    }
+
+    #[test]
+    fn test_record_limit() {
+        let mut counter = Counter(0);
+        let recorder = record(&mut counter, |_| Image::new(1, 1), 1, 3);
+
+        assert_eq!(3, recorder.len());
+        assert_eq!(2, counter.0);
+    }
+}
@@ -0,0 +1,317 @@
+use crate::grid::{neighbors, Connectivity, Grid2D};
+
+/// Connected group of grid cells
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    // sorted in row order
+    cells: Vec<(usize, usize)>,
+    perimeter: usize,
+    min: (usize, usize),
+    max: (usize, usize),
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Cell edges that don't touch another cell of the region, the grid border counts as outside
+    pub fn perimeter(&self) -> usize {
+        self.perimeter
+    }
+
+    /// Top left and bottom right corner of the bounding box, both inclusive
+    pub fn bounds(&self) -> ((usize, usize), (usize, usize)) {
+        (self.min, self.max)
+    }
+
+    pub fn width(&self) -> usize {
+        self.max.0 - self.min.0 + 1
+    }
+
+    pub fn height(&self) -> usize {
+        self.max.1 - self.min.1 + 1
+    }
+
+    /// Member cells in row order
+    pub fn cells(&self) -> &[(usize, usize)] {
+        &self.cells
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.cells
+            .binary_search_by_key(&(y, x), |&(cx, cy)| (cy, cx))
+            .is_ok()
+    }
+}
+
+/// Region id of every cell of a grid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Labels {
+    ids: Vec<Option<usize>>,
+    width: usize,
+    height: usize,
+    regions: Vec<Region>,
+}
+
+impl Labels {
+    /// Ids index into `regions`
+    pub fn regions(&self) -> &[Region] {
+        &self.regions
+    }
+
+    pub fn len(&self) -> usize {
+        self.regions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.regions.is_empty()
+    }
+
+    pub fn label_at(&self, x: usize, y: usize) -> Option<usize> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.ids[y * self.width + x]
+    }
+
+    pub fn region_at(&self, x: usize, y: usize) -> Option<&Region> {
+        self.label_at(x, y).map(|id| &self.regions[id])
+    }
+}
+
+impl Grid2D for Labels {
+    type Cell = Option<usize>;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        Some(self.ids[y * self.width + x])
+    }
+}
+
+/// Region of cells matching `include` that can be reached from `start`, None if `start` itself
+/// doesn't match
+pub fn flood_fill<G, F>(
+    grid: &G,
+    start: (usize, usize),
+    connectivity: Connectivity,
+    include: F,
+) -> Option<Region>
+where
+    G: Grid2D,
+    F: Fn(&G::Cell) -> bool,
+{
+    let (width, height) = (grid.width(), grid.height());
+    let is_match = |x, y| grid.cell(x, y).is_some_and(|cell| include(&cell));
+
+    if !is_match(start.0, start.1) {
+        return None;
+    }
+
+    let mut ids = vec![None; width * height];
+    Some(fill(
+        &is_match,
+        &mut ids,
+        (width, height),
+        start,
+        connectivity,
+        0,
+    ))
+}
+
+/// Splits the cells matching `include` into connected regions, which are numbered in the order
+/// their first cell appears in row order
+pub fn label<G, F>(grid: &G, connectivity: Connectivity, include: F) -> Labels
+where
+    G: Grid2D,
+    F: Fn(&G::Cell) -> bool,
+{
+    let (width, height) = (grid.width(), grid.height());
+    let matches = (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .map(|(x, y)| grid.cell(x, y).is_some_and(|cell| include(&cell)))
+        .collect::<Vec<_>>();
+    let is_match = |x: usize, y: usize| matches[y * width + x];
+
+    let mut ids = vec![None; width * height];
+    let mut regions = vec![];
+
+    for y in 0..height {
+        for x in 0..width {
+            if !is_match(x, y) || ids[y * width + x].is_some() {
+                continue;
+            }
+
+            let id = regions.len();
+            regions.push(fill(
+                &is_match,
+                &mut ids,
+                (width, height),
+                (x, y),
+                connectivity,
+                id,
+            ));
+        }
+    }
+
+    Labels {
+        ids,
+        width,
+        height,
+        regions,
+    }
+}
+
+// breadth first from `start`, marks every reached cell with `id`
+fn fill<F: Fn(usize, usize) -> bool>(
+    is_match: &F,
+    ids: &mut [Option<usize>],
+    (width, height): (usize, usize),
+    start: (usize, usize),
+    connectivity: Connectivity,
+    id: usize,
+) -> Region {
+    ids[start.1 * width + start.0] = Some(id);
+
+    // the cells double as the queue
+    let mut cells = vec![start];
+    let mut next = 0;
+
+    while let Some(&pos) = cells.get(next) {
+        next += 1;
+
+        for (x, y) in neighbors(pos, width, height, connectivity) {
+            if ids[y * width + x].is_none() && is_match(x, y) {
+                ids[y * width + x] = Some(id);
+                cells.push((x, y));
+            }
+        }
+    }
+
+    cells.sort_unstable_by_key(|&(x, y)| (y, x));
+
+    let perimeter = cells
+        .iter()
+        .map(|&pos| {
+            4 - neighbors(pos, width, height, Connectivity::Four)
+                .filter(|&(x, y)| ids[y * width + x] == Some(id))
+                .count()
+        })
+        .sum();
+
+    let min = (cells.iter().map(|c| c.0).min().unwrap(), cells[0].1);
+    let max = (
+        cells.iter().map(|c| c.0).max().unwrap(),
+        cells[cells.len() - 1].1,
+    );
+
+    Region {
+        cells,
+        perimeter,
+        min,
+        max,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{Connectivity, FlatGrid, Grid2D};
+    use crate::region::{flood_fill, label};
+
+    const MAP: &str = "\
+##..#
+#..##
+..#..
+##..#";
+
+    fn map() -> Vec<char> {
+        MAP.lines().flat_map(|line| line.chars()).collect()
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let cells = map();
+        let grid = FlatGrid::new(&cells, 5);
+
+        let region = flood_fill(&grid, (0, 0), Connectivity::Four, |c| *c == '#').unwrap();
+        assert_eq!(vec![(0, 0), (1, 0), (0, 1)], region.cells());
+        assert_eq!(3, region.area());
+        assert_eq!(8, region.perimeter());
+        assert_eq!(((0, 0), (1, 1)), region.bounds());
+        assert!(region.contains(0, 1));
+        assert!(!region.contains(1, 1));
+
+        let region = flood_fill(&grid, (4, 0), Connectivity::Eight, |c| *c == '#').unwrap();
+        assert_eq!(6, region.area());
+        assert_eq!(((0, 0), (4, 3)), region.bounds());
+
+        assert_eq!(
+            None,
+            flood_fill(&grid, (2, 0), Connectivity::Four, |c| *c == '#')
+        );
+    }
+
+    #[test]
+    fn test_label() {
+        let cells = map();
+        let grid = FlatGrid::new(&cells, 5);
+
+        let four = label(&grid, Connectivity::Four, |c| *c == '#');
+        assert_eq!(5, four.len());
+        assert_eq!(Some(0), four.label_at(1, 0));
+        assert_eq!(Some(1), four.label_at(4, 1));
+        assert_eq!(None, four.label_at(2, 0));
+        assert_eq!(Some(2), four.region_at(1, 3).map(|r| r.area()));
+        assert_eq!(
+            vec![3, 3, 1, 2, 1],
+            four.regions().iter().map(|r| r.area()).collect::<Vec<_>>()
+        );
+
+        let eight = label(&grid, Connectivity::Eight, |c| *c == '#');
+        assert_eq!(3, eight.len());
+        assert_eq!(eight.label_at(4, 0), eight.label_at(1, 3));
+        assert_eq!(Some(Some(2)), eight.cell(4, 3));
+
+        let runs = label(&grid, Connectivity::Horizontal, |c| *c == '.');
+        assert_eq!(5, runs.len());
+        assert!(runs
+            .regions()
+            .iter()
+            .all(|r| (r.width(), r.height()) == (2, 1)));
+        assert_eq!(((3, 2), (4, 2)), runs.regions()[3].bounds());
+    }
+
+    #[test]
+    fn test_perimeter() {
+        let plus = ".#.\n###\n.#."
+            .chars()
+            .filter(|c| *c != '\n')
+            .collect::<Vec<_>>();
+        let grid = FlatGrid::new(&plus, 3);
+
+        let region = flood_fill(&grid, (1, 1), Connectivity::Four, |c| *c == '#').unwrap();
+        assert_eq!(12, region.perimeter());
+
+        let ring = "###\n#.#\n###"
+            .chars()
+            .filter(|c| *c != '\n')
+            .collect::<Vec<_>>();
+        let grid = FlatGrid::new(&ring, 3);
+
+        let region = flood_fill(&grid, (0, 0), Connectivity::Four, |c| *c == '#').unwrap();
+        assert_eq!(8, region.area());
+        assert_eq!(16, region.perimeter());
+    }
+}
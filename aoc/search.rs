@@ -0,0 +1,509 @@
+use crate::answer::Answer;
+use crate::grid::{neighbors, Connectivity, Grid2D};
+use crate::memo::Memo;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+/// Anything that can list the successors of a node
+pub trait Graph {
+    type Node: Clone + Eq + Hash;
+
+    fn successors(&self, node: &Self::Node) -> Vec<Self::Node>;
+
+    /// Cost of moving from `from` to its successor `to`, used by dijkstra and astar
+    fn cost(&self, _from: &Self::Node, _to: &Self::Node) -> u64 {
+        1
+    }
+}
+
+/// Graph backed by a successor closure
+pub struct FnGraph<N, F> {
+    successors: F,
+    node: PhantomData<N>,
+}
+
+pub fn from_fn<N, F>(successors: F) -> FnGraph<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<N>,
+{
+    FnGraph {
+        successors,
+        node: PhantomData,
+    }
+}
+
+impl<N, F> Graph for FnGraph<N, F>
+where
+    N: Clone + Eq + Hash,
+    F: Fn(&N) -> Vec<N>,
+{
+    type Node = N;
+
+    fn successors(&self, node: &N) -> Vec<N> {
+        (self.successors)(node)
+    }
+}
+
+/// Graph over the (x, y) positions of a grid, cells are connected when both are passable
+pub struct GridGraph<'a, G, F> {
+    grid: &'a G,
+    passable: F,
+    connectivity: Connectivity,
+}
+
+impl<'a, G, F> GridGraph<'a, G, F>
+where
+    G: Grid2D,
+    F: Fn(&G::Cell) -> bool,
+{
+    pub fn new(grid: &'a G, connectivity: Connectivity, passable: F) -> Self {
+        GridGraph {
+            grid,
+            passable,
+            connectivity,
+        }
+    }
+
+    /// All passable positions, useful as input for `connected_components`
+    pub fn nodes(&self) -> Vec<(usize, usize)> {
+        (0..self.grid.height())
+            .flat_map(|y| (0..self.grid.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.is_passable(x, y))
+            .collect()
+    }
+
+    fn is_passable(&self, x: usize, y: usize) -> bool {
+        self.grid
+            .cell(x, y)
+            .is_some_and(|cell| (self.passable)(&cell))
+    }
+}
+
+impl<G, F> Graph for GridGraph<'_, G, F>
+where
+    G: Grid2D,
+    F: Fn(&G::Cell) -> bool,
+{
+    type Node = (usize, usize);
+
+    fn successors(&self, &pos: &Self::Node) -> Vec<Self::Node> {
+        neighbors(
+            pos,
+            self.grid.width(),
+            self.grid.height(),
+            self.connectivity,
+        )
+        .filter(|&(x, y)| self.is_passable(x, y))
+        .collect()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N> {
+    /// every node from start to goal, both included
+    pub nodes: Vec<N>,
+    pub cost: u64,
+}
+
+impl<N> Path<N> {
+    pub fn goal(&self) -> &N {
+        self.nodes.last().expect("path should never be empty")
+    }
+}
+
+fn rebuild_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, goal: N, cost: u64) -> Path<N> {
+    let mut nodes = vec![goal];
+
+    while let Some(parent) = parents.get(nodes.last().unwrap()) {
+        nodes.push(parent.clone());
+    }
+
+    nodes.reverse();
+    Path { nodes, cost }
+}
+
+/// Shortest path by number of steps to the first node matching `is_goal`
+pub fn bfs<G, P>(graph: &G, start: G::Node, is_goal: P) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    P: Fn(&G::Node) -> bool,
+{
+    let mut parents = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(rebuild_path(&parents, node, steps));
+        }
+
+        for next in graph.successors(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Cheapest path to the first node matching `is_goal` using `Graph::cost`
+pub fn dijkstra<G, P>(graph: &G, start: G::Node, is_goal: P) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    P: Fn(&G::Node) -> bool,
+{
+    astar(graph, start, is_goal, |_| 0)
+}
+
+/// Like dijkstra but guided by `heuristic`, which must never overestimate the remaining cost. It
+/// doesn't have to be consistent, nodes get expanded again once a cheaper way to them turns up.
+pub fn astar<G, P, H>(graph: &G, start: G::Node, is_goal: P, heuristic: H) -> Option<Path<G::Node>>
+where
+    G: Graph,
+    P: Fn(&G::Node) -> bool,
+    H: Fn(&G::Node) -> u64,
+{
+    let mut parents = HashMap::new();
+    let mut costs = HashMap::from([(start.clone(), 0)]);
+
+    // nodes aren't required to be Ord so the heap only holds indices into `nodes`
+    let mut nodes = vec![start.clone()];
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), 0, 0))]);
+
+    while let Some(Reverse((_, cost, index))) = queue.pop() {
+        let node = nodes[index].clone();
+
+        // a cheaper way to the node was found after this entry got queued
+        if costs[&node] < cost {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some(rebuild_path(&parents, node, cost));
+        }
+
+        for next in graph.successors(&node) {
+            let next_cost = cost + graph.cost(&node, &next);
+
+            if costs.get(&next).is_some_and(|&known| known <= next_cost) {
+                continue;
+            }
+
+            costs.insert(next.clone(), next_cost);
+            parents.insert(next.clone(), node.clone());
+            queue.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+
+    None
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct CycleError;
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "graph contains a cycle")
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Orders all nodes reachable from `starts` so every node comes before its successors
+pub fn topological_sort<G, I>(graph: &G, starts: I) -> Result<Vec<G::Node>, CycleError>
+where
+    G: Graph,
+    I: IntoIterator<Item = G::Node>,
+{
+    let mut order = vec![];
+    let mut done = HashSet::new();
+    let mut in_progress = HashSet::new();
+
+    for start in starts {
+        let mut stack = vec![(start, false)];
+
+        while let Some((node, expanded)) = stack.pop() {
+            if expanded {
+                in_progress.remove(&node);
+                done.insert(node.clone());
+                order.push(node);
+                continue;
+            }
+
+            if done.contains(&node) {
+                continue;
+            }
+
+            if !in_progress.insert(node.clone()) {
+                return Err(CycleError);
+            }
+
+            let successors = graph.successors(&node);
+            stack.push((node, true));
+
+            for next in successors {
+                if in_progress.contains(&next) {
+                    return Err(CycleError);
+                }
+
+                if !done.contains(&next) {
+                    stack.push((next, false));
+                }
+            }
+        }
+    }
+
+    order.reverse();
+    Ok(order)
+}
+
+/// Number of distinct paths from `start` that end in a node matching `is_goal`, a path may
+/// continue past a goal node. The graph has to be acyclic.
+pub fn count_paths<G, P>(graph: &G, start: G::Node, is_goal: P) -> Answer
+where
+    G: Graph,
+    P: Fn(&G::Node) -> bool,
+{
+    let mut memo = Memo::new();
+
+    memo.get_or_compute_iter(
+        start,
+        |node| graph.successors(node),
+        |node, counts: &[&Answer]| {
+            Answer::from(is_goal(node) as u8) + counts.iter().copied().sum::<Answer>()
+        },
+    )
+    .clone()
+}
+
+/// Groups `nodes` into sets of nodes reachable from each other, assumes the successors are
+/// symmetric (if b is a successor of a, a is a successor of b)
+pub fn connected_components<G, I>(graph: &G, nodes: I) -> Vec<Vec<G::Node>>
+where
+    G: Graph,
+    I: IntoIterator<Item = G::Node>,
+{
+    let mut seen = HashSet::new();
+    let mut components = vec![];
+
+    for node in nodes {
+        if !seen.insert(node.clone()) {
+            continue;
+        }
+
+        let mut component = vec![];
+        let mut queue = VecDeque::from([node]);
+
+        while let Some(node) = queue.pop_front() {
+            for next in graph.successors(&node) {
+                if seen.insert(next.clone()) {
+                    queue.push_back(next);
+                }
+            }
+
+            component.push(node);
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod test {
+    use crate::answer::Answer;
+    use crate::grid::{Connectivity, FlatGrid};
+    use crate::search::{
+        astar, bfs, connected_components, count_paths, dijkstra, from_fn, topological_sort,
+        CycleError, Graph, GridGraph,
+    };
+
+    const MAZE: &str = "\
+S..#....
+.#.#.##.
+.#...#..
+.####.#.
+......#G";
+
+    fn maze() -> (Vec<char>, usize) {
+        (MAZE.chars().filter(|c| *c != '\n').collect(), 8)
+    }
+
+    #[test]
+    fn test_bfs_grid() {
+        let (cells, width) = maze();
+        let grid = FlatGrid::new(&cells, width);
+        let graph = GridGraph::new(&grid, Connectivity::Four, |c| *c != '#');
+
+        let path = bfs(&graph, (0, 0), |&pos| pos == (7, 4)).expect("should find a path");
+
+        assert_eq!(15, path.cost);
+        assert_eq!(16, path.nodes.len());
+        assert_eq!((0, 0), path.nodes[0]);
+        assert_eq!(&(7, 4), path.goal());
+
+        // every step moves exactly one cell
+        assert!(path
+            .nodes
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+
+        assert_eq!(None, bfs(&graph, (0, 0), |&pos| pos == (3, 0)));
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        let (cells, width) = maze();
+        let grid = FlatGrid::new(&cells, width);
+        let graph = GridGraph::new(&grid, Connectivity::Four, |c| *c != '#');
+
+        let path = astar(
+            &graph,
+            (0, 0),
+            |&pos| pos == (7, 4),
+            |&(x, y)| (x.abs_diff(7) + y.abs_diff(4)) as u64,
+        )
+        .expect("should find a path");
+
+        assert_eq!(15, path.cost);
+    }
+
+    struct Weighted;
+
+    impl Graph for Weighted {
+        type Node = char;
+
+        fn successors(&self, node: &char) -> Vec<char> {
+            match node {
+                'a' => vec!['b', 'c'],
+                'b' => vec!['d'],
+                'c' => vec!['b', 'd'],
+                _ => vec![],
+            }
+        }
+
+        fn cost(&self, from: &char, to: &char) -> u64 {
+            match (from, to) {
+                ('a', 'b') => 10,
+                ('a', 'c') => 1,
+                ('c', 'b') => 1,
+                ('b', 'd') => 1,
+                ('c', 'd') => 5,
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let path = dijkstra(&Weighted, 'a', |n| *n == 'd').expect("should find a path");
+        assert_eq!(vec!['a', 'c', 'b', 'd'], path.nodes);
+        assert_eq!(3, path.cost);
+
+        let path = bfs(&Weighted, 'a', |n| *n == 'd').expect("should find a path");
+        assert_eq!(2, path.cost);
+    }
+
+    // s-a-c-g costs 5, s-b-c-g costs 7
+    struct Detour;
+
+    impl Graph for Detour {
+        type Node = char;
+
+        fn successors(&self, node: &char) -> Vec<char> {
+            match node {
+                's' => vec!['a', 'b'],
+                'a' | 'b' => vec!['c'],
+                'c' => vec!['g'],
+                _ => vec![],
+            }
+        }
+
+        fn cost(&self, from: &char, to: &char) -> u64 {
+            match (from, to) {
+                ('s', _) | ('a', 'c') => 1,
+                ('b', 'c') | ('c', 'g') => 3,
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_astar_inconsistent_heuristic() {
+        // never overestimates, but puts off a so that c is first reached via b
+        let heuristic = |node: &char| if *node == 'a' { 4 } else { 0 };
+
+        let path = astar(&Detour, 's', |n| *n == 'g', heuristic).expect("should find a path");
+        assert_eq!(vec!['s', 'a', 'c', 'g'], path.nodes);
+        assert_eq!(5, path.cost);
+    }
+
+    #[test]
+    fn test_topological_sort() {
+        let order = topological_sort(&Weighted, ['a']).expect("should be acyclic");
+        assert_eq!(vec!['a', 'c', 'b', 'd'], order);
+
+        let cyclic = from_fn(|n: &u32| vec![(n + 1) % 4]);
+        assert_eq!(Err(CycleError), topological_sort(&cyclic, [0]));
+    }
+
+    #[test]
+    fn test_count_paths() {
+        assert_eq!(
+            Answer::from(3u64),
+            count_paths(&Weighted, 'a', |n| *n == 'd')
+        );
+        assert_eq!(
+            Answer::from(5u64),
+            count_paths(&Weighted, 'a', |n| *n == 'b' || *n == 'd')
+        );
+
+        // lattice paths through a 10x10 grid
+        let lattice = from_fn(|&(x, y): &(u32, u32)| {
+            let mut next = vec![];
+            if x < 10 {
+                next.push((x + 1, y));
+            }
+            if y < 10 {
+                next.push((x, y + 1));
+            }
+            next
+        });
+        assert_eq!(
+            Answer::from(184756u64),
+            count_paths(&lattice, (0, 0), |&p| p == (10, 10))
+        );
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let cells = "##..#\n#...#\n...##\n#.#.."
+            .chars()
+            .filter(|c| *c != '\n')
+            .collect::<Vec<char>>();
+        let grid = FlatGrid::new(&cells, 5);
+
+        let walls = GridGraph::new(&grid, Connectivity::Four, |c| *c == '#');
+        let mut sizes = connected_components(&walls, walls.nodes())
+            .iter()
+            .map(|c| c.len())
+            .collect::<Vec<usize>>();
+        sizes.sort();
+        assert_eq!(vec![1, 1, 3, 4], sizes);
+
+        let walls = GridGraph::new(&grid, Connectivity::Eight, |c| *c == '#');
+        assert_eq!(3, connected_components(&walls, walls.nodes()).len());
+    }
+}
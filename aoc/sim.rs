@@ -0,0 +1,310 @@
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Something that advances in discrete steps
+pub trait Simulation {
+    /// Advances one step, false once there is nothing left to do
+    fn step(&mut self) -> bool;
+}
+
+/// Gets called by the `Driver` before the first and after every step
+pub trait Observer<S> {
+    fn on_start(&mut self, _sim: &S) {}
+
+    fn on_step(&mut self, step: usize, sim: &S);
+
+    fn on_finish(&mut self, _sim: &S, _stats: &Stats) {}
+}
+
+impl<S, F: FnMut(usize, &S)> Observer<S> for F {
+    fn on_step(&mut self, step: usize, sim: &S) {
+        self(step, sim)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StopReason {
+    /// `step` returned false
+    Finished,
+    /// a step didn't change the state
+    FixedPoint,
+    MaxSteps,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    /// steps that returned true
+    pub steps: usize,
+    pub stop: StopReason,
+    pub elapsed: Duration,
+    /// calls to `step`, including the last one
+    pub step_times: StepTimes,
+}
+
+impl Stats {
+    pub fn slowest_step(&self) -> Option<(usize, Duration)> {
+        self.step_times.slowest
+    }
+
+    pub fn fastest_step(&self) -> Option<(usize, Duration)> {
+        self.step_times.fastest
+    }
+
+    pub fn mean_step_time(&self) -> Duration {
+        match self.step_times.calls {
+            0 => Duration::ZERO,
+            n => self.step_times.total / n as u32,
+        }
+    }
+}
+
+/// Running totals over the calls to `step`, so long runs don't keep a sample per step
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StepTimes {
+    pub calls: usize,
+    pub total: Duration,
+    /// index of the call and its duration, the first one wins ties
+    pub fastest: Option<(usize, Duration)>,
+    pub slowest: Option<(usize, Duration)>,
+}
+
+impl StepTimes {
+    pub fn add(&mut self, time: Duration) {
+        let call = (self.calls, time);
+
+        if self.fastest.is_none_or(|(_, fastest)| time < fastest) {
+            self.fastest = Some(call);
+        }
+
+        if self.slowest.is_none_or(|(_, slowest)| time > slowest) {
+            self.slowest = Some(call);
+        }
+
+        self.calls += 1;
+        self.total += time;
+    }
+}
+
+impl fmt::Display for Stats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} steps ({:?}) in {:?}, {:?} per step",
+            self.steps,
+            self.stop,
+            self.elapsed,
+            self.mean_step_time()
+        )?;
+
+        if let Some((step, time)) = self.slowest_step() {
+            write!(f, ", slowest was step {} with {time:?}", step + 1)?;
+        }
+
+        Ok(())
+    }
+}
+
+// returns true if the state equals the one it saw last time
+type FixedPointCheck<'a, S> = Box<dyn FnMut(&S) -> bool + 'a>;
+
+/// Runs a `Simulation` until it is done or one of the configured limits is hit
+pub struct Driver<'a, S> {
+    max_steps: Option<usize>,
+    fixed_point: Option<FixedPointCheck<'a, S>>,
+    observers: Vec<Box<dyn Observer<S> + 'a>>,
+}
+
+impl<'a, S: Simulation> Driver<'a, S> {
+    pub fn new() -> Self {
+        Driver {
+            max_steps: None,
+            fixed_point: None,
+            observers: vec![],
+        }
+    }
+
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = Some(max_steps);
+        self
+    }
+
+    /// Stops as soon as a step leaves the state unchanged, clones the state after every step
+    pub fn stop_at_fixed_point(mut self) -> Self
+    where
+        S: Clone + PartialEq + 'a,
+    {
+        let mut previous: Option<S> = None;
+
+        self.fixed_point = Some(Box::new(move |sim: &S| {
+            let same = previous.as_ref() == Some(sim);
+            previous = Some(sim.clone());
+            same
+        }));
+
+        self
+    }
+
+    pub fn observe<O: Observer<S> + 'a>(mut self, observer: O) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    pub fn run(&mut self, sim: &mut S) -> Stats {
+        let start = Instant::now();
+        let mut step_times = StepTimes::default();
+
+        for observer in self.observers.iter_mut() {
+            observer.on_start(sim);
+        }
+
+        if let Some(fixed_point) = &mut self.fixed_point {
+            fixed_point(sim);
+        }
+
+        let stop = loop {
+            if self.max_steps.is_some_and(|max| step_times.calls >= max) {
+                break StopReason::MaxSteps;
+            }
+
+            let step_start = Instant::now();
+            let changed = sim.step();
+            step_times.add(step_start.elapsed());
+
+            if !changed {
+                break StopReason::Finished;
+            }
+
+            for observer in self.observers.iter_mut() {
+                observer.on_step(step_times.calls, sim);
+            }
+
+            if let Some(fixed_point) = &mut self.fixed_point
+                && fixed_point(sim)
+            {
+                break StopReason::FixedPoint;
+            }
+        };
+
+        let steps = match stop {
+            StopReason::Finished => step_times.calls - 1,
+            _ => step_times.calls,
+        };
+
+        let stats = Stats {
+            steps,
+            stop,
+            elapsed: start.elapsed(),
+            step_times,
+        };
+
+        for observer in self.observers.iter_mut() {
+            observer.on_finish(sim, &stats);
+        }
+
+        stats
+    }
+}
+
+impl<S: Simulation> Default for Driver<'_, S> {
+    fn default() -> Self {
+        Driver::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::sim::{Driver, Observer, Simulation, Stats, StepTimes, StopReason};
+    use std::time::Duration;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Countdown(u32);
+
+    impl Simulation for Countdown {
+        fn step(&mut self) -> bool {
+            if self.0 == 0 {
+                return false;
+            }
+
+            self.0 -= 1;
+            true
+        }
+    }
+
+    // keeps reporting a change but stops changing at 3
+    #[derive(Debug, Clone, PartialEq)]
+    struct Saturate(u32);
+
+    impl Simulation for Saturate {
+        fn step(&mut self) -> bool {
+            self.0 = (self.0 + 1).min(3);
+            true
+        }
+    }
+
+    #[test]
+    fn test_run_until_finished() {
+        let mut seen = vec![];
+        let stats = Driver::new()
+            .observe(|step, sim: &Countdown| seen.push((step, sim.0)))
+            .run(&mut Countdown(3));
+
+        assert_eq!(3, stats.steps);
+        assert_eq!(StopReason::Finished, stats.stop);
+        assert_eq!(4, stats.step_times.calls);
+        assert_eq!(vec![(1, 2), (2, 1), (3, 0)], seen);
+    }
+
+    #[test]
+    fn test_limits() {
+        let mut sim = Countdown(100);
+        let stats = Driver::new().max_steps(10).run(&mut sim);
+        assert_eq!(StopReason::MaxSteps, stats.stop);
+        assert_eq!(10, stats.steps);
+        assert_eq!(90, sim.0);
+
+        let mut sim = Saturate(0);
+        let stats = Driver::new().stop_at_fixed_point().run(&mut sim);
+        assert_eq!(StopReason::FixedPoint, stats.stop);
+        assert_eq!(4, stats.steps);
+        assert_eq!(3, sim.0);
+    }
+
+    #[test]
+    fn test_step_times() {
+        let mut times = StepTimes::default();
+
+        for millis in [3, 1, 5, 1, 5] {
+            times.add(Duration::from_millis(millis));
+        }
+
+        assert_eq!(5, times.calls);
+        assert_eq!(Duration::from_millis(15), times.total);
+        assert_eq!(Some((1, Duration::from_millis(1))), times.fastest);
+        assert_eq!(Some((2, Duration::from_millis(5))), times.slowest);
+    }
+
+    #[test]
+    fn test_observer_hooks() {
+        #[derive(Default)]
+        struct Log(Vec<String>);
+
+        impl Observer<Countdown> for &mut Log {
+            fn on_start(&mut self, sim: &Countdown) {
+                self.0.push(format!("start {}", sim.0));
+            }
+
+            fn on_step(&mut self, step: usize, sim: &Countdown) {
+                self.0.push(format!("{step}: {}", sim.0));
+            }
+
+            fn on_finish(&mut self, _: &Countdown, stats: &Stats) {
+                self.0.push(format!("done after {}", stats.steps));
+            }
+        }
+
+        let mut log = Log::default();
+        Driver::new().observe(&mut log).run(&mut Countdown(2));
+
+        assert_eq!(vec!["start 2", "1: 1", "2: 0", "done after 2"], log.0);
+    }
+}
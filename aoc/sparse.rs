@@ -0,0 +1,248 @@
+use crate::grid::Connectivity;
+use std::collections::HashMap;
+
+pub type Pos = (i64, i64);
+
+/// Inclusive rectangle covering every occupied position of a `SparseGrid`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bounds {
+    pub min: Pos,
+    pub max: Pos,
+}
+
+impl Bounds {
+    fn point(pos: Pos) -> Self {
+        Bounds { min: pos, max: pos }
+    }
+
+    pub fn width(&self) -> u64 {
+        self.min.0.abs_diff(self.max.0) + 1
+    }
+
+    pub fn height(&self) -> u64 {
+        self.min.1.abs_diff(self.max.1) + 1
+    }
+
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        (self.min.0..=self.max.0).contains(&x) && (self.min.1..=self.max.1).contains(&y)
+    }
+
+    fn extend(&mut self, (x, y): Pos) {
+        self.min = (self.min.0.min(x), self.min.1.min(y));
+        self.max = (self.max.0.max(x), self.max.1.max(y));
+    }
+}
+
+/// Unbounded grid that only stores occupied cells, coordinates may be negative
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Pos, T>,
+    bounds: Option<Bounds>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    /// Parses text with the first line at y = 0, `parse` returns None for empty cells
+    pub fn from_text<F>(input: &str, mut parse: F) -> Self
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut grid = SparseGrid::new();
+
+        for (y, line) in input.lines().enumerate() {
+            for (x, c) in line.trim_end().chars().enumerate() {
+                if let Some(cell) = parse(c) {
+                    grid.insert((x as i64, y as i64), cell);
+                }
+            }
+        }
+
+        grid
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Smallest rectangle containing all cells, None while the grid is empty
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        self.cells.get_mut(&pos)
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn insert(&mut self, pos: Pos, value: T) -> Option<T> {
+        match &mut self.bounds {
+            Some(bounds) => bounds.extend(pos),
+            None => self.bounds = Some(Bounds::point(pos)),
+        }
+
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Pos) -> Option<T> {
+        let removed = self.cells.remove(&pos)?;
+
+        // only a cell on the edge can shrink the bounding box
+        if let Some(bounds) = self.bounds
+            && (pos.0 == bounds.min.0
+                || pos.0 == bounds.max.0
+                || pos.1 == bounds.min.1
+                || pos.1 == bounds.max.1)
+        {
+            self.bounds = self.cells.keys().fold(None, |acc, &pos| match acc {
+                Some(mut bounds) => {
+                    bounds.extend(pos);
+                    Some(bounds)
+                }
+                None => Some(Bounds::point(pos)),
+            });
+        }
+
+        Some(removed)
+    }
+
+    /// Cells in arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells.iter().map(|(pos, cell)| (*pos, cell))
+    }
+
+    /// Occupied neighbors of `pos`
+    pub fn neighbors(
+        &self,
+        (x, y): Pos,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (Pos, &T)> {
+        connectivity.offsets().iter().filter_map(move |&(dx, dy)| {
+            let pos = (x + dx as i64, y + dy as i64);
+            self.cells.get(&pos).map(|cell| (pos, cell))
+        })
+    }
+
+    /// One line per row of the bounding box, `to_char` also gets called for empty cells
+    pub fn render<F>(&self, mut to_char: F) -> String
+    where
+        F: FnMut(Option<&T>) -> char,
+    {
+        let Some(bounds) = self.bounds else {
+            return String::new();
+        };
+
+        let mut str = String::with_capacity(((bounds.width() + 1) * bounds.height()) as usize);
+
+        for y in bounds.min.1..=bounds.max.1 {
+            if y > bounds.min.1 {
+                str.push('\n');
+            }
+
+            for x in bounds.min.0..=bounds.max.0 {
+                str.push(to_char(self.cells.get(&(x, y))));
+            }
+        }
+
+        str
+    }
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid::new()
+    }
+}
+
+impl<T> FromIterator<(Pos, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Pos, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+
+        for (pos, cell) in iter {
+            grid.insert(pos, cell);
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::Connectivity;
+    use crate::sparse::{Bounds, SparseGrid};
+
+    #[test]
+    fn test_bounds() {
+        let mut g = SparseGrid::new();
+        assert_eq!(None, g.bounds());
+
+        g.insert((0, 0), 'a');
+        g.insert((-3, 2), 'b');
+        g.insert((5, -1), 'c');
+        assert_eq!(
+            Some(Bounds {
+                min: (-3, -1),
+                max: (5, 2)
+            }),
+            g.bounds()
+        );
+        assert_eq!(9, g.bounds().unwrap().width());
+        assert_eq!(4, g.bounds().unwrap().height());
+
+        assert_eq!(Some('c'), g.remove((5, -1)));
+        assert_eq!(None, g.remove((5, -1)));
+        assert_eq!(
+            Some(Bounds {
+                min: (-3, 0),
+                max: (0, 2)
+            }),
+            g.bounds()
+        );
+
+        g.remove((0, 0));
+        g.remove((-3, 2));
+        assert!(g.is_empty());
+        assert_eq!(None, g.bounds());
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let g = SparseGrid::from_text("#.#\n.#.\n#..", |c| (c == '#').then_some(()));
+        assert_eq!(4, g.len());
+
+        assert_eq!(0, g.neighbors((1, 1), Connectivity::Four).count());
+        assert_eq!(3, g.neighbors((1, 1), Connectivity::Eight).count());
+        assert_eq!(
+            vec![(0, 0)],
+            g.neighbors((-1, -1), Connectivity::Eight)
+                .map(|(pos, _)| pos)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let g = [((-1, -1), 'a'), ((1, 0), 'b')]
+            .into_iter()
+            .collect::<SparseGrid<_>>();
+
+        assert_eq!("a..\n..b", g.render(|cell| cell.copied().unwrap_or('.')));
+        assert_eq!("", SparseGrid::<char>::new().render(|_| '.'));
+    }
+}
@@ -0,0 +1,281 @@
+use crate::grid::Grid2D;
+
+/// Clockwise rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rotation {
+    R90,
+    R180,
+    R270,
+}
+
+/// Rotated, flipped or transposed view of a grid, nothing gets copied. Chaining transforms only
+/// updates the orientation so views don't nest.
+#[derive(Debug, Clone, Copy)]
+pub struct View<G> {
+    grid: G,
+    // applied to view coordinates in this order: mirror x, mirror y, swap x and y
+    flip_x: bool,
+    flip_y: bool,
+    transpose: bool,
+}
+
+impl<G: Grid2D> View<G> {
+    pub fn new(grid: G) -> Self {
+        View {
+            grid,
+            flip_x: false,
+            flip_y: false,
+            transpose: false,
+        }
+    }
+
+    /// Mirrors along the main diagonal, rows become columns
+    pub fn transpose(self) -> Self {
+        View {
+            flip_x: self.flip_y,
+            flip_y: self.flip_x,
+            transpose: !self.transpose,
+            ..self
+        }
+    }
+
+    /// Mirrors left to right
+    pub fn flip_h(self) -> Self {
+        View {
+            flip_x: !self.flip_x,
+            ..self
+        }
+    }
+
+    /// Mirrors top to bottom
+    pub fn flip_v(self) -> Self {
+        View {
+            flip_y: !self.flip_y,
+            ..self
+        }
+    }
+
+    pub fn rotate(self, rotation: Rotation) -> Self {
+        match rotation {
+            Rotation::R90 => self.transpose().flip_h(),
+            Rotation::R180 => self.flip_h().flip_v(),
+            Rotation::R270 => self.transpose().flip_v(),
+        }
+    }
+
+    /// Rectangle of this view, see `SubGrid::new`
+    pub fn sub(self, x: usize, y: usize, width: usize, height: usize) -> SubGrid<Self> {
+        SubGrid::new(self, x, y, width, height)
+    }
+
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+}
+
+impl<G: Grid2D> Grid2D for View<G> {
+    type Cell = G::Cell;
+
+    fn width(&self) -> usize {
+        match self.transpose {
+            true => self.grid.height(),
+            false => self.grid.width(),
+        }
+    }
+
+    fn height(&self) -> usize {
+        match self.transpose {
+            true => self.grid.width(),
+            false => self.grid.height(),
+        }
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        if x >= self.width() || y >= self.height() {
+            return None;
+        }
+
+        let x = if self.flip_x { self.width() - 1 - x } else { x };
+        let y = if self.flip_y {
+            self.height() - 1 - y
+        } else {
+            y
+        };
+
+        match self.transpose {
+            true => self.grid.cell(y, x),
+            false => self.grid.cell(x, y),
+        }
+    }
+}
+
+/// Rectangular window into a grid
+#[derive(Debug, Clone, Copy)]
+pub struct SubGrid<G> {
+    grid: G,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+}
+
+impl<G: Grid2D> SubGrid<G> {
+    /// The rectangle gets clipped to the grid
+    pub fn new(grid: G, x: usize, y: usize, width: usize, height: usize) -> Self {
+        let width = width.min(grid.width().saturating_sub(x));
+        let height = height.min(grid.height().saturating_sub(y));
+
+        SubGrid {
+            grid,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn view(self) -> View<Self> {
+        View::new(self)
+    }
+}
+
+impl<G: Grid2D> Grid2D for SubGrid<G> {
+    type Cell = G::Cell;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+
+        self.grid.cell(self.x + x, self.y + y)
+    }
+}
+
+/// Block of text as a grid of chars, short lines are padded with spaces
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextGrid {
+    lines: Vec<Vec<char>>,
+    width: usize,
+}
+
+impl TextGrid {
+    pub fn new(text: &str) -> Self {
+        let lines = text
+            .lines()
+            .map(|line| line.trim_end_matches('\r').chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let width = lines.iter().map(|line| line.len()).max().unwrap_or(0);
+
+        TextGrid { lines, width }
+    }
+}
+
+impl Grid2D for TextGrid {
+    type Cell = char;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.lines.len()
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<Self::Cell> {
+        let line = self.lines.get(y)?;
+
+        match x < self.width {
+            true => Some(line.get(x).copied().unwrap_or(' ')),
+            false => None,
+        }
+    }
+}
+
+/// One line per row
+pub fn to_text<G: Grid2D<Cell = char>>(grid: &G) -> String {
+    (0..grid.height())
+        .map(|y| {
+            (0..grid.width())
+                .filter_map(|x| grid.cell(x, y))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{FlatGrid, Grid2D};
+    use crate::transform::{to_text, Rotation, SubGrid, TextGrid, View};
+
+    const TEXT: &str = "abc\ndef";
+
+    fn transformed(f: impl Fn(View<&TextGrid>) -> View<&TextGrid>) -> String {
+        to_text(&f(View::new(&TextGrid::new(TEXT))))
+    }
+
+    #[test]
+    fn test_orientations() {
+        assert_eq!(TEXT, transformed(|v| v));
+        assert_eq!("ad\nbe\ncf", transformed(|v| v.transpose()));
+        assert_eq!("cba\nfed", transformed(|v| v.flip_h()));
+        assert_eq!("def\nabc", transformed(|v| v.flip_v()));
+        assert_eq!("da\neb\nfc", transformed(|v| v.rotate(Rotation::R90)));
+        assert_eq!("fed\ncba", transformed(|v| v.rotate(Rotation::R180)));
+        assert_eq!("cf\nbe\nad", transformed(|v| v.rotate(Rotation::R270)));
+    }
+
+    #[test]
+    fn test_compositions() {
+        assert_eq!(TEXT, transformed(|v| v.transpose().transpose()));
+        assert_eq!(
+            TEXT,
+            transformed(|v| v.rotate(Rotation::R90).rotate(Rotation::R270))
+        );
+        assert_eq!(
+            transformed(|v| v.rotate(Rotation::R180)),
+            transformed(|v| v.rotate(Rotation::R90).rotate(Rotation::R90))
+        );
+        assert_eq!(
+            transformed(|v| v.rotate(Rotation::R270)),
+            transformed(|v| v.flip_h().transpose())
+        );
+        assert_eq!(
+            transformed(|v| v.transpose().flip_h().flip_v()),
+            transformed(|v| v.flip_v().flip_h().transpose())
+        );
+    }
+
+    #[test]
+    fn test_sub_grid() {
+        let cells = (0..20).collect::<Vec<_>>();
+        let grid = FlatGrid::new(&cells, 5);
+
+        let sub = SubGrid::new(&grid, 1, 2, 10, 10);
+        assert_eq!((4, 2), (sub.width(), sub.height()));
+        assert_eq!(Some(11), sub.cell(0, 0));
+        assert_eq!(Some(19), sub.cell(3, 1));
+        assert_eq!(None, sub.cell(4, 0));
+
+        let rotated = View::new(&grid).rotate(Rotation::R90).sub(1, 0, 2, 2);
+        assert_eq!(Some(10), rotated.cell(0, 0));
+        assert_eq!(Some(6), rotated.cell(1, 1));
+    }
+
+    #[test]
+    fn test_text_grid() {
+        let grid = TextGrid::new("ab\r\nc");
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+        assert_eq!(Some(' '), grid.cell(1, 1));
+        assert_eq!(None, grid.cell(2, 0));
+        assert_eq!("ab\nc ", to_text(&grid));
+    }
+}
@@ -0,0 +1,333 @@
+use crate::ansi::ColorMode;
+use crate::sim::Simulation;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+/// A simulation that can be driven by the stepper
+pub trait Stepper: Simulation + Clone {
+    fn view(&self, mode: ColorMode) -> String;
+
+    /// Shown in the side panel
+    fn counters(&self) -> Vec<(&'static str, String)> {
+        vec![]
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Step(usize),
+    Back(usize),
+    Jump(usize),
+    Run,
+    Pause,
+    Quit,
+}
+
+impl Command {
+    pub fn parse(line: &str) -> Option<Command> {
+        let mut parts = line.split_whitespace();
+        let command = parts.next().unwrap_or("s");
+        let count = match parts.next() {
+            Some(count) => Some(count.parse().ok()?),
+            None => None,
+        };
+
+        if parts.next().is_some() {
+            return None;
+        }
+
+        match (command, count) {
+            ("s" | "step", count) => Some(Command::Step(count.unwrap_or(1))),
+            ("b" | "back", count) => Some(Command::Back(count.unwrap_or(1))),
+            ("j" | "jump", Some(step)) => Some(Command::Jump(step)),
+            ("r" | "run", None) => Some(Command::Run),
+            ("p" | "pause", None) => Some(Command::Pause),
+            ("q" | "quit", None) => Some(Command::Quit),
+            _ => None,
+        }
+    }
+}
+
+const HELP: &str = "[enter] step  s N  b N  j N  r(un)  p(ause)  q(uit)";
+
+/// Every visited state, so stepping back is just moving the cursor
+#[derive(Debug, Clone)]
+pub struct History<S> {
+    states: Vec<S>,
+    cursor: usize,
+    finished: bool,
+}
+
+impl<S: Stepper> History<S> {
+    pub fn new(initial: S) -> Self {
+        History {
+            states: vec![initial],
+            cursor: 0,
+            finished: false,
+        }
+    }
+
+    pub fn current(&self) -> &S {
+        &self.states[self.cursor]
+    }
+
+    pub fn step_index(&self) -> usize {
+        self.cursor
+    }
+
+    /// True if the current state is the last one
+    pub fn is_finished(&self) -> bool {
+        self.finished && self.cursor + 1 == self.states.len()
+    }
+
+    /// Moves one step forward, false if the simulation is done
+    pub fn forward(&mut self) -> bool {
+        if self.cursor + 1 < self.states.len() {
+            self.cursor += 1;
+            return true;
+        }
+
+        if self.finished {
+            return false;
+        }
+
+        let mut next = self.current().clone();
+
+        if !next.step() {
+            self.finished = true;
+            return false;
+        }
+
+        self.states.push(next);
+        self.cursor += 1;
+        true
+    }
+
+    pub fn back(&mut self, steps: usize) {
+        self.cursor = self.cursor.saturating_sub(steps);
+    }
+
+    /// Goes to `step`, or the last step if the simulation finishes earlier
+    pub fn jump(&mut self, step: usize) {
+        if step <= self.cursor {
+            self.cursor = step;
+            return;
+        }
+
+        while self.cursor < step && self.forward() {}
+    }
+}
+
+/// Interactive stepper on stdin/stdout, reads one command per line
+pub fn run<S: Stepper>(initial: S, fps: u32) -> io::Result<()> {
+    let delay = Duration::from_secs_f64(1.0 / f64::from(fps.max(1)));
+    let mut history = History::new(initial);
+    let mut running = false;
+    let mut message = String::new();
+
+    // stdin is read on its own thread so running can be interrupted
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        for line in io::stdin().lock().lines() {
+            if line.is_err() || tx.send(line.unwrap_or_default()).is_err() {
+                break;
+            }
+        }
+    });
+
+    loop {
+        draw(&history, running, &message)?;
+        message.clear();
+
+        let line = match running {
+            true => match rx.recv_timeout(delay) {
+                // any input while running only pauses
+                Ok(_) => {
+                    running = false;
+                    continue;
+                }
+                Err(RecvTimeoutError::Timeout) => {
+                    running = history.forward();
+                    continue;
+                }
+                // no more input, finish the run first
+                Err(RecvTimeoutError::Disconnected) => {
+                    std::thread::sleep(delay);
+                    running = history.forward();
+                    continue;
+                }
+            },
+            false => match rx.recv() {
+                Ok(line) => line,
+                Err(_) => return Ok(()),
+            },
+        };
+
+        match Command::parse(&line) {
+            Some(Command::Step(steps)) => {
+                for _ in 0..steps {
+                    if !history.forward() {
+                        break;
+                    }
+                }
+            }
+            Some(Command::Back(steps)) => history.back(steps),
+            Some(Command::Jump(step)) => history.jump(step),
+            Some(Command::Run) => running = true,
+            Some(Command::Pause) => running = false,
+            Some(Command::Quit) => return Ok(()),
+            None => message = format!("unknown command: {line}"),
+        }
+    }
+}
+
+fn draw<S: Stepper>(history: &History<S>, running: bool, message: &str) -> io::Result<()> {
+    let state = history.current();
+
+    let status = match (running, history.is_finished()) {
+        (_, true) => "finished",
+        (true, false) => "running",
+        (false, false) => "paused",
+    };
+
+    let mut panel = vec![
+        format!("step: {}", history.step_index()),
+        format!("state: {status}"),
+        String::new(),
+    ];
+    panel.extend(
+        state
+            .counters()
+            .into_iter()
+            .map(|(name, value)| format!("{name}: {value}")),
+    );
+
+    let mut out = io::stdout().lock();
+    // clear the screen and move to the top left
+    write!(out, "\x1b[2J\x1b[H")?;
+    writeln!(out, "{}", layout(&state.view(ColorMode::Auto), &panel))?;
+    writeln!(out, "\n{HELP}")?;
+
+    if !message.is_empty() {
+        writeln!(out, "{message}")?;
+    }
+
+    write!(out, "> ")?;
+    out.flush()
+}
+
+/// Puts the panel to the right of the view
+pub fn layout(view: &str, panel: &[String]) -> String {
+    let lines = view.lines().collect::<Vec<_>>();
+    let width = lines
+        .iter()
+        .map(|line| visible_len(line))
+        .max()
+        .unwrap_or(0);
+
+    (0..lines.len().max(panel.len()))
+        .map(|i| {
+            let line = lines.get(i).copied().unwrap_or("");
+            let padding = " ".repeat(width - visible_len(line));
+
+            match panel.get(i).filter(|entry| !entry.is_empty()) {
+                Some(entry) => format!("{line}{padding}  │ {entry}"),
+                None => format!("{line}{padding}  │"),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// length without ANSI escape sequences
+fn visible_len(line: &str) -> usize {
+    let mut len = 0;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+            continue;
+        }
+
+        len += 1;
+    }
+
+    len
+}
+
+#[cfg(test)]
+mod test {
+    use crate::ansi::ColorMode;
+    use crate::sim::Simulation;
+    use crate::tui::{layout, visible_len, Command, History, Stepper};
+
+    #[derive(Clone)]
+    struct Counter(u32);
+
+    impl Simulation for Counter {
+        fn step(&mut self) -> bool {
+            if self.0 == 5 {
+                return false;
+            }
+
+            self.0 += 1;
+            true
+        }
+    }
+
+    impl Stepper for Counter {
+        fn view(&self, _: ColorMode) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Some(Command::Step(1)), Command::parse(""));
+        assert_eq!(Some(Command::Step(10)), Command::parse("s 10"));
+        assert_eq!(Some(Command::Back(1)), Command::parse("back"));
+        assert_eq!(Some(Command::Jump(42)), Command::parse("j 42"));
+        assert_eq!(Some(Command::Run), Command::parse(" r "));
+        assert_eq!(None, Command::parse("j"));
+        assert_eq!(None, Command::parse("s x"));
+        assert_eq!(None, Command::parse("r 1"));
+        assert_eq!(None, Command::parse("what"));
+    }
+
+    #[test]
+    fn test_history() {
+        let mut history = History::new(Counter(0));
+
+        assert!(history.forward());
+        assert!(history.forward());
+        assert_eq!(2, history.current().0);
+
+        history.back(5);
+        assert_eq!(0, history.step_index());
+        assert_eq!(0, history.current().0);
+
+        history.jump(100);
+        assert_eq!(5, history.step_index());
+        assert!(history.is_finished());
+        assert!(!history.forward());
+
+        history.jump(3);
+        assert_eq!(3, history.current().0);
+        assert!(!history.is_finished());
+        assert!(history.forward());
+    }
+
+    #[test]
+    fn test_layout() {
+        assert_eq!(3, visible_len("\x1b[1;32m#.#\x1b[0m"));
+        assert_eq!(
+            "ab  │ step: 1\n\x1b[32mc\x1b[0m   │\n    │ x: 2",
+            layout(
+                "ab\n\x1b[32mc\x1b[0m",
+                &["step: 1".to_string(), String::new(), "x: 2".to_string()]
+            )
+        );
+    }
+}